@@ -972,6 +972,87 @@ impl<F: MatchFunc> Aligner<F> {
     }
 }
 
+/// Compute the score of the optimal local alignment of `x` and `y` without building the
+/// traceback. Only two rows of the dynamic programming matrices are kept, so memory usage is
+/// linear in the length of `y`. The clipping penalties of `scoring` are ignored.
+///
+/// The score is the same as the one of the alignment returned by `Aligner::local`.
+///
+/// # Example
+///
+/// ```
+/// use bio::alignment::pairwise::{smith_waterman_score, Scoring};
+///
+/// let scoring = Scoring::from_scores(-5, -1, 1, -1);
+/// assert_eq!(smith_waterman_score(b"TTACGTAA", b"GGACGTGG", &scoring), 4);
+/// ```
+pub fn smith_waterman_score<F: MatchFunc>(x: TextSlice, y: TextSlice, scoring: &Scoring<F>) -> i32 {
+    score_only(x, y, scoring, true)
+}
+
+/// Compute the score of the optimal global alignment of `x` and `y` without building the
+/// traceback. Only two rows of the dynamic programming matrices are kept, so memory usage is
+/// linear in the length of `y`. The clipping penalties of `scoring` are ignored.
+///
+/// The score is the same as the one of the alignment returned by `Aligner::global`.
+///
+/// # Example
+///
+/// ```
+/// use bio::alignment::pairwise::{needleman_wunsch_score, Scoring};
+///
+/// let scoring = Scoring::from_scores(-5, -1, 1, -1);
+/// assert_eq!(needleman_wunsch_score(b"ACGTACGT", b"ACGACGT", &scoring), 1);
+/// ```
+pub fn needleman_wunsch_score<F: MatchFunc>(
+    x: TextSlice,
+    y: TextSlice,
+    scoring: &Scoring<F>,
+) -> i32 {
+    score_only(x, y, scoring, false)
+}
+
+/// Affine gap dynamic programming over the rows of x, keeping only the previous row of S and
+/// the current row of I. D only depends on the cell to the left and is kept as a scalar.
+fn score_only<F: MatchFunc>(x: TextSlice, y: TextSlice, scoring: &Scoring<F>, local: bool) -> i32 {
+    let n = y.len();
+    let gap = |k: usize| scoring.gap_open + scoring.gap_extend * k as i32;
+    let floor = if local { 0 } else { MIN_SCORE };
+
+    let mut s_prev: Vec<i32> = (0..=n)
+        .map(|j| if local || j == 0 { 0 } else { gap(j) })
+        .collect();
+    let mut s_curr = vec![0; n + 1];
+    let mut i_curr = vec![MIN_SCORE; n + 1];
+    let mut best = 0;
+
+    for (i, &a) in x.iter().enumerate() {
+        s_curr[0] = if local { 0 } else { gap(i + 1) };
+        let mut d = MIN_SCORE;
+        for (j, &b) in y.iter().enumerate() {
+            let j = j + 1;
+            i_curr[j] = max(
+                i_curr[j] + scoring.gap_extend,
+                s_prev[j] + scoring.gap_open + scoring.gap_extend,
+            );
+            d = max(
+                d + scoring.gap_extend,
+                s_curr[j - 1] + scoring.gap_open + scoring.gap_extend,
+            );
+            let m = s_prev[j - 1] + scoring.match_fn.score(a, b);
+            s_curr[j] = max(max(m, floor), max(i_curr[j], d));
+            best = max(best, s_curr[j]);
+        }
+        std::mem::swap(&mut s_prev, &mut s_curr);
+    }
+
+    if local {
+        best
+    } else {
+        s_prev[n]
+    }
+}
+
 /// Packed representation of one cell of a Smith-Waterman traceback matrix.
 /// Stores the I, D and S traceback matrix values in two bytes.
 /// Possible traceback moves include : start, insert, delete, match, substitute,
@@ -1641,4 +1722,40 @@ mod tests {
             assert_eq!(alignment.score, 0);
         }
     }
+
+    #[test]
+    fn test_score_only() {
+        let pairs: [(&[u8], &[u8]); 6] = [
+            (b"ACCGTGGAT", b"AAAAACCGTTGAT"),
+            (b"GAAAACCGTTGAT", b"ACCGTGGATGGG"),
+            (
+                b"AGCACACGTGTGCGCTATACAGTAAGTAGTAGTACACGTGTCACAGTTGTACTAGCATGAC",
+                b"AGCACACGTGTGCGCTATACAGTACACGTGTCACAGTTGTACTAGCATGAC",
+            ),
+            (b"TTTT", b"AAAA"),
+            (b"ACGT", b""),
+            (b"", b""),
+        ];
+        let scorings = [
+            Scoring::from_scores(-5, -1, 1, -1),
+            Scoring::from_scores(-2, -3, 2, -4),
+        ];
+        for scoring in scorings.iter() {
+            for &(x, y) in pairs.iter() {
+                let mut aligner = Aligner::with_scoring(scoring.clone());
+                assert_eq!(
+                    needleman_wunsch_score(x, y, scoring),
+                    aligner.global(x, y).score
+                );
+                assert_eq!(
+                    needleman_wunsch_score(y, x, scoring),
+                    aligner.global(y, x).score
+                );
+                assert_eq!(
+                    smith_waterman_score(x, y, scoring),
+                    aligner.local(x, y).score
+                );
+            }
+        }
+    }
 }
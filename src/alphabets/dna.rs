@@ -66,6 +66,22 @@ pub fn complement(a: u8) -> u8 {
     COMPLEMENT[a as usize]
 }
 
+/// Calculate the complement of given text without reversing it (IUPAC alphabet supported).
+///
+/// Ambiguity codes are complemented to the code of the complementary bases, e.g. `R` (A or G)
+/// → `Y` (C or T), while `S`, `W` and `N` remain as they are. Casing of characters is
+/// preserved.
+///
+/// ```
+/// use bio::alphabets::dna;
+///
+/// assert_eq!(dna::complement_iupac(b"ACGTN"), b"TGCAN");
+/// assert_eq!(dna::complement_iupac(b"GaTryK"), b"CtAyrM");
+/// ```
+pub fn complement_iupac(text: &[u8]) -> Vec<u8> {
+    text.iter().map(|&a| complement(a)).collect()
+}
+
 /// Calculate reverse complement of given text (IUPAC alphabet supported).
 ///
 /// Casing of characters is preserved, e.g. `b"NaCgT"` → `b"aCgTN"`.
@@ -109,6 +125,23 @@ mod tests {
         assert!(!alphabet().is_word(b"#"));
     }
 
+    #[test]
+    fn test_complement_iupac() {
+        assert_eq!(
+            complement_iupac(b"ACGTRYSWKMBDHVN"),
+            b"TGCAYRSWMKVHDBN".to_vec()
+        );
+        assert_eq!(
+            complement_iupac(b"acgtryswkmbdhvn"),
+            b"tgcayrswmkvhdbn".to_vec()
+        );
+        assert_eq!(complement_iupac(b"AcGtRyNn"), b"TgCaYrNn".to_vec());
+        assert_eq!(
+            complement_iupac(&complement_iupac(b"ACGTRYSWKMBDHVN")),
+            b"ACGTRYSWKMBDHVN".to_vec()
+        );
+    }
+
     #[test]
     fn number_is_no_word() {
         assert!(!alphabet().is_word(b"42"));
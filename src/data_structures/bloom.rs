@@ -0,0 +1,148 @@
+//! A Bloom filter over k-mers: a compact set that answers membership queries without storing
+//! the k-mers themselves, at the price of occasional false positives.
+//!
+//! The filter is sized from the expected number of k-mers `n` and the tolerated false positive
+//! rate `p` with the standard formulas: `m = -n ln(p) / ln(2)^2` bits and `m / n ln(2)` hash
+//! functions. The hash functions are derived from two independent hashes of the k-mer by double
+//! hashing (Kirsch and Mitzenmacher, 2006).
+//!
+//! # Example
+//!
+//! ```
+//! use bio::data_structures::bloom::KmerBloomFilter;
+//! use bio::data_structures::kmer::Kmer;
+//!
+//! let mut filter = KmerBloomFilter::new(1000, 0.01);
+//! filter.insert(&Kmer::from_literal("GATTACA"));
+//! assert!(filter.contains(&Kmer::from_literal("GATTACA")));
+//! ```
+
+use bv::{BitVec, Bits, BitsMut};
+
+use crate::data_structures::kmer::{mix64, Kmer};
+
+/// A Bloom filter of k-mers, sized for an expected number of k-mers and a false positive rate.
+/// K-mers are hashed by their bases, so equal k-mers are found regardless of the unused bits of
+/// their encoding.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KmerBloomFilter {
+    bits: BitVec<u64>,
+    num_hashes: u32,
+}
+
+impl KmerBloomFilter {
+    /// Create an empty filter for about `expected_items` k-mers, such that the false positive
+    /// rate is at most `false_positive_rate` as long as no more k-mers are inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not strictly between 0 and 1.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        assert!(
+            false_positive_rate > 0.0 && false_positive_rate < 1.0,
+            "false positive rate must be between 0 and 1"
+        );
+        let n = expected_items.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let len = (-n * false_positive_rate.ln() / (ln2 * ln2))
+            .ceil()
+            .max(1.0);
+        let num_hashes = (len / n * ln2).round().max(1.0) as u32;
+        KmerBloomFilter {
+            bits: BitVec::new_fill(false, len as u64),
+            num_hashes,
+        }
+    }
+
+    /// The number of bits of the filter.
+    pub fn len(&self) -> u64 {
+        self.bits.len()
+    }
+
+    /// Whether the filter has no bits, which never happens.
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// The number of hash functions, i.e. of bits set per k-mer.
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+
+    /// Add `kmer` to the filter by setting its `num_hashes` bits.
+    pub fn insert(&mut self, kmer: &Kmer) {
+        for bit in self.positions(kmer) {
+            self.bits.set_bit(bit, true);
+        }
+    }
+
+    /// Whether `kmer` may have been inserted. False positives are possible, false negatives are
+    /// not: an inserted k-mer is always reported as present.
+    pub fn contains(&self, kmer: &Kmer) -> bool {
+        self.positions(kmer).all(|bit| self.bits.get_bit(bit))
+    }
+
+    /// The bits of `kmer`, `h1 + i * h2` for each hash function `i`.
+    fn positions(&self, kmer: &Kmer) -> impl Iterator<Item = u64> {
+        let h1 = kmer.fold_to(64);
+        let h2 = mix64(h1) | 1;
+        let len = self.bits.len();
+        (0..u64::from(self.num_hashes)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structures::test_utils::random_seq;
+
+    #[test]
+    fn test_sizing() {
+        let filter = KmerBloomFilter::new(1000, 0.01);
+        // 9.59 bits and 6.64 hash functions per item
+        assert_eq!(filter.len(), 9586);
+        assert_eq!(filter.num_hashes(), 7);
+        assert!(!filter.is_empty());
+        assert_eq!(KmerBloomFilter::new(0, 0.5).num_hashes(), 1);
+    }
+
+    #[test]
+    fn test_no_false_negatives() {
+        let k = 15;
+        let seq = random_seq(5000, 1);
+        let kmers: Vec<Kmer> = seq.windows(k).map(|w| Kmer::new(k, w)).collect();
+        let mut filter = KmerBloomFilter::new(kmers.len(), 0.01);
+        for kmer in &kmers {
+            filter.insert(kmer);
+        }
+        assert!(kmers.iter().all(|kmer| filter.contains(kmer)));
+
+        let other = random_seq(5000, 2);
+        let false_positives = other
+            .windows(k)
+            .filter(|w| filter.contains(&Kmer::new(k, w)))
+            .count();
+        assert!(false_positives < 150);
+    }
+
+    #[test]
+    fn test_dirty_padding() {
+        let clean = Kmer::from_literal("GATTACA");
+        let mut dirty = clean.clone();
+        *dirty.sequence.last_mut().unwrap() |= 0b1100_0000;
+
+        let mut filter = KmerBloomFilter::new(10, 0.01);
+        filter.insert(&clean);
+        assert!(filter.contains(&dirty));
+
+        let mut filter = KmerBloomFilter::new(10, 0.01);
+        filter.insert(&dirty);
+        assert!(filter.contains(&clean));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_rate() {
+        KmerBloomFilter::new(10, 1.0);
+    }
+}
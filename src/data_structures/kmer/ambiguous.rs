@@ -0,0 +1,255 @@
+//! K-mers over the IUPAC nucleotide alphabet.
+//!
+//! Each base is stored in 4 bits as the set of nucleotides it stands for (`A` = 1, `C` = 2,
+//! `G` = 4, `T` = 8), such that e.g. `R` (A or G) is 5 and `N` is 15. Two bases are packed into
+//! each byte, the first one in the low bits. This is twice the size of the 2-bit `Kmer`, so
+//! `AmbiguousKmer` is meant for messy input that needs to be handled before it can be converted
+//! with `AmbiguousKmer::to_kmer`.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::data_structures::kmer::ambiguous::AmbiguousKmer;
+//! use bio::data_structures::kmer::Kmer;
+//!
+//! let kmer = AmbiguousKmer::from_literal("GATNACA");
+//! assert_eq!(kmer.decode(), "GATNACA");
+//! assert!(kmer.to_kmer().is_err());
+//!
+//! let kmer = AmbiguousKmer::from_literal("GATTACA");
+//! assert_eq!(kmer.to_kmer(), Ok(Kmer::from_literal("GATTACA")));
+//! ```
+
+use std::fmt;
+
+use crate::data_structures::kmer::{Error, Kmer, Result};
+
+/// Characters of the 4-bit codes. Code 0 (no nucleotide) is never produced by encoding.
+const IUPAC_SYMBOLS: &[u8; 16] = b"-ACMGRSVTWYHKDBN";
+
+/// Return the 4-bit code of an IUPAC nucleotide in either case, reading `U` as `T` like `Kmer`.
+fn iupac_code(nucleotide: u8) -> Option<u8> {
+    match nucleotide.to_ascii_uppercase() {
+        b'-' => None,
+        b'U' => Some(8),
+        upper => IUPAC_SYMBOLS
+            .iter()
+            .position(|&symbol| symbol == upper)
+            .map(|code| code as u8),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AmbiguousKmer {
+    pub k: usize,
+    pub sequence: Vec<u8>,
+}
+
+impl AmbiguousKmer {
+    /// Create a k-mer of length `len` from the IUPAC nucleotides in `byte_seq`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte_seq` does not have length `len` or contains a character that is not an
+    /// IUPAC nucleotide. See `AmbiguousKmer::try_new` for a fallible version.
+    pub fn new(len: usize, byte_seq: &[u8]) -> Self {
+        AmbiguousKmer::try_new(len, byte_seq).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a k-mer of length `len` from the IUPAC nucleotides in `byte_seq`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SequenceLengthMismatch` if `byte_seq` does not have length `len`, and
+    /// `Error::InvalidNucleotide` for the first character that is not an IUPAC nucleotide (in
+    /// either case, with `U` read as `T`).
+    pub fn try_new(len: usize, byte_seq: &[u8]) -> Result<Self> {
+        if len != byte_seq.len() {
+            return Err(Error::SequenceLengthMismatch {
+                k: len,
+                found: byte_seq.len(),
+            });
+        }
+        let mut kmer = AmbiguousKmer {
+            k: len,
+            sequence: Vec::with_capacity(len.div_ceil(2)),
+        };
+        kmer.try_encode(byte_seq)?;
+        Ok(kmer)
+    }
+
+    /// Create a k-mer from a string of IUPAC nucleotides.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `str_literal` contains a character that is not an IUPAC nucleotide.
+    pub fn from_literal(str_literal: &str) -> Self {
+        AmbiguousKmer::new(str_literal.len(), str_literal.as_bytes())
+    }
+
+    /// Replace the contents of the k-mer with the 4-bit encoding of `byte_seq`, setting `k` to
+    /// its length. Lowercase is encoded like uppercase and `U` like `T`, so decoding yields
+    /// uppercase DNA.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte_seq` contains a character that is not an IUPAC nucleotide. See
+    /// `AmbiguousKmer::try_encode` for a fallible version.
+    pub fn encode(&mut self, byte_seq: &[u8]) {
+        if let Err(e) = self.try_encode(byte_seq) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Replace the contents of the k-mer with the 4-bit encoding of `byte_seq`, setting `k` to
+    /// its length.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidNucleotide` for the first character that is not an IUPAC
+    /// nucleotide. The k-mer is left unchanged in that case.
+    pub fn try_encode(&mut self, byte_seq: &[u8]) -> Result<()> {
+        let codes = byte_seq
+            .iter()
+            .enumerate()
+            .map(|(position, &byte)| {
+                iupac_code(byte).ok_or(Error::InvalidNucleotide { byte, position })
+            })
+            .collect::<Result<Vec<u8>>>()?;
+        self.k = byte_seq.len();
+        self.sequence.clear();
+        self.sequence.extend(
+            codes
+                .chunks(2)
+                .map(|pair| pair[0] | pair.get(1).map_or(0, |&code| code << 4)),
+        );
+        Ok(())
+    }
+
+    /// Decode the k-mer into its IUPAC nucleotide sequence.
+    pub fn decode(&self) -> String {
+        (0..self.k)
+            .map(|i| char::from(IUPAC_SYMBOLS[self.code(i) as usize]))
+            .collect()
+    }
+
+    /// Return true if at least one base is ambiguous, i.e. not exactly one of `A`, `C`, `G` or
+    /// `T`.
+    pub fn is_ambiguous(&self) -> bool {
+        (0..self.k).any(|i| self.code(i).count_ones() != 1)
+    }
+
+    /// Convert into a 2-bit `Kmer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::AmbiguousNucleotide` for the first ambiguous base.
+    pub fn to_kmer(&self) -> Result<Kmer> {
+        let bases = self.decode();
+        if let Some((position, byte)) = bases
+            .bytes()
+            .enumerate()
+            .find(|&(i, _)| self.code(i).count_ones() != 1)
+        {
+            return Err(Error::AmbiguousNucleotide { byte, position });
+        }
+        Kmer::try_new(self.k, bases.as_bytes())
+    }
+
+    /// Return the 4-bit code of the base at `position`.
+    fn code(&self, position: usize) -> u8 {
+        (self.sequence[position / 2] >> (4 * (position % 2))) & 0b1111
+    }
+}
+
+impl From<&Kmer> for AmbiguousKmer {
+    fn from(kmer: &Kmer) -> Self {
+        AmbiguousKmer::from_literal(&kmer.decode())
+    }
+}
+
+impl fmt::Display for AmbiguousKmer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.decode())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let symbols = "ACGTRYSWKMBDHVN";
+        let kmer = AmbiguousKmer::from_literal(symbols);
+        assert_eq!(kmer.sequence.len(), 8);
+        assert_eq!(kmer.decode(), symbols);
+        assert_eq!(kmer.to_string(), symbols);
+        assert_eq!(AmbiguousKmer::from_literal("").decode(), "");
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            AmbiguousKmer::try_new(4, b"ACXT"),
+            Err(Error::InvalidNucleotide {
+                byte: b'X',
+                position: 2
+            })
+        );
+        assert_eq!(
+            AmbiguousKmer::try_new(2, b"A-"),
+            Err(Error::InvalidNucleotide {
+                byte: b'-',
+                position: 1
+            })
+        );
+
+        let mut kmer = AmbiguousKmer::from_literal("NNN");
+        assert!(kmer.try_encode(b"ACX").is_err());
+        assert_eq!(kmer.decode(), "NNN");
+        kmer.encode(b"RYS");
+        assert_eq!(kmer.decode(), "RYS");
+        assert_eq!(AmbiguousKmer::from_literal("acgurn").decode(), "ACGTRN");
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        assert_eq!(
+            AmbiguousKmer::try_new(3, b"ACGTA"),
+            Err(Error::SequenceLengthMismatch { k: 3, found: 5 })
+        );
+        assert_eq!(
+            AmbiguousKmer::try_new(6, b"ACGTA"),
+            Err(Error::SequenceLengthMismatch { k: 6, found: 5 })
+        );
+
+        // re-encoding a shorter sequence shrinks the k-mer
+        let mut kmer = AmbiguousKmer::from_literal("NNN");
+        kmer.encode(b"A");
+        assert_eq!(kmer.k, 1);
+        assert_eq!(kmer.decode(), "A");
+        assert_eq!(kmer, AmbiguousKmer::from_literal("A"));
+        kmer.encode(b"GATNACA");
+        assert_eq!(kmer.decode(), "GATNACA");
+    }
+
+    #[test]
+    fn test_to_kmer() {
+        let kmer = AmbiguousKmer::from_literal("GATTACA");
+        assert!(!kmer.is_ambiguous());
+        assert_eq!(kmer.to_kmer(), Ok(Kmer::from_literal("GATTACA")));
+        assert_eq!(AmbiguousKmer::from(&Kmer::from_literal("GATTACA")), kmer);
+
+        let kmer = AmbiguousKmer::from_literal("GATRACN");
+        assert!(kmer.is_ambiguous());
+        assert_eq!(
+            kmer.to_kmer(),
+            Err(Error::AmbiguousNucleotide {
+                byte: b'R',
+                position: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,871 @@
+//! Counting of k-mers over DNA sequences.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::mem;
+use std::thread;
+
+use bv::{BitVec, Bits, BitsMut};
+
+use crate::data_structures::kmer::{byte_to_nuc, nuc_to_byte, Error, Kmer, Result};
+
+/// Largest k for which `distinct_kmers_bitset` uses a bitset of all 4^k k-mers (4 MiB at most).
+pub const MAX_BITSET_K: usize = 14;
+
+/// Count the number of distinct k-mers of `seq` exactly.
+///
+/// For `k <= MAX_BITSET_K`, each k-mer is marked in a bitset of length 4^k indexed by its 2-bit
+/// packed value, which is faster and smaller than hashing. Larger k fall back to a `HashSet`.
+///
+/// # Panics
+///
+/// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::counter::distinct_kmers_bitset;
+///
+/// assert_eq!(distinct_kmers_bitset(b"AAAAC", 2), 2);
+/// ```
+pub fn distinct_kmers_bitset(seq: &[u8], k: usize) -> u64 {
+    if k == 0 || seq.len() < k {
+        return 0;
+    }
+    if k > MAX_BITSET_K {
+        return seq
+            .windows(k)
+            .map(|window| Kmer::new(k, window))
+            .collect::<HashSet<Kmer>>()
+            .len() as u64;
+    }
+
+    let mut seen: BitVec<u64> = BitVec::new_fill(false, 1 << (2 * k));
+    let mut distinct = 0;
+    let mut packed = 0u64;
+    for (i, &nuc) in seq.iter().enumerate() {
+        // the first base of the window occupies the lowest bits, as in `Kmer`
+        packed = (packed >> 2) | (u64::from(nuc_to_byte(char::from(nuc))) << (2 * (k - 1)));
+        if i + 1 >= k && !seen.get_bit(packed) {
+            seen.set_bit(packed, true);
+            distinct += 1;
+        }
+    }
+    distinct
+}
+
+/// A table of the number of occurrences of each k-mer.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::counter::KmerCounter;
+/// use bio::data_structures::kmer::Kmer;
+///
+/// let counter = KmerCounter::from_sequence(b"AAAAT", 2, true);
+/// assert_eq!(counter.get(&Kmer::from_literal("AA")), 3);
+/// // AT is its own reverse complement
+/// assert_eq!(counter.get(&Kmer::from_literal("AT")), 1);
+/// assert_eq!(counter.len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct KmerCounter {
+    k: usize,
+    canonical: bool,
+    counts: HashMap<Kmer, u64>,
+}
+
+impl KmerCounter {
+    /// Create an empty counter of k-mers of length `k`. If `canonical` is true, a k-mer and its
+    /// reverse complement are counted together.
+    pub fn new(k: usize, canonical: bool) -> Self {
+        KmerCounter {
+            k,
+            canonical,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Count the k-mers of `seq`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn from_sequence(seq: &[u8], k: usize, canonical: bool) -> Self {
+        let mut counter = KmerCounter::new(k, canonical);
+        counter.add_sequence(seq);
+        counter
+    }
+
+    /// Count the k-mers of `seq` on all available cores. The sequence is split into one chunk
+    /// per thread, with consecutive chunks overlapping by `k - 1` bases such that each k-mer is
+    /// counted exactly once, and the per-chunk counts are merged. The result is the same as
+    /// with `KmerCounter::from_sequence`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn from_sequence_parallel(seq: &[u8], k: usize, canonical: bool) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        KmerCounter::from_sequence_chunked(seq, k, canonical, threads)
+    }
+
+    fn from_sequence_chunked(seq: &[u8], k: usize, canonical: bool, chunks: usize) -> Self {
+        if k == 0 || seq.len() < k {
+            return KmerCounter::new(k, canonical);
+        }
+        let windows = seq.len() - k + 1;
+        // a chunk of fewer than 2^16 k-mers is not worth a thread
+        let chunks = chunks.min(windows.div_ceil(1 << 16)).max(1);
+        let windows_per_chunk = windows.div_ceil(chunks);
+        let counters: Vec<KmerCounter> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..windows)
+                .step_by(windows_per_chunk)
+                .map(|start| {
+                    let end = (start + windows_per_chunk).min(windows) + k - 1;
+                    let chunk = &seq[start..end];
+                    scope.spawn(move || KmerCounter::from_sequence(chunk, k, canonical))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        });
+        let mut counters = counters.into_iter();
+        let mut counter = counters.next().unwrap();
+        for other in counters {
+            for (kmer, count) in other.counts {
+                *counter.counts.entry(kmer).or_insert(0) += count;
+            }
+        }
+        counter
+    }
+
+    /// Add the k-mers of `seq` to the counts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn add_sequence(&mut self, seq: &[u8]) {
+        let k = self.k;
+        if k == 0 || seq.len() < k {
+            return;
+        }
+        // Roll the k-mer (and its reverse complement) along the sequence instead of encoding
+        // every window, and only clone it into the table when it is seen for the first time.
+        let mut forward = Kmer::new(k, &seq[..k]);
+        let mut revcomp = if self.canonical {
+            Some(forward.make_reverse_complement())
+        } else {
+            None
+        };
+        for (i, &nuc) in seq.iter().enumerate().skip(k - 1) {
+            if i >= k {
+                forward.push_back(nuc);
+                if let Some(revcomp) = revcomp.as_mut() {
+                    // complement via the 2-bit code, which also covers lowercase and U
+                    revcomp.push_front(byte_to_nuc(3 - nuc_to_byte(char::from(nuc))) as u8);
+                }
+            }
+            let key = match revcomp {
+                Some(ref revcomp) => (&forward).min(revcomp),
+                None => &forward,
+            };
+            match self.counts.get_mut(key) {
+                Some(count) => *count += 1,
+                None => {
+                    self.counts.insert(key.clone(), 1);
+                }
+            }
+        }
+    }
+
+    /// The number of occurrences of `kmer` (together with its reverse complement if the counter
+    /// is canonical).
+    pub fn get(&self, kmer: &Kmer) -> u64 {
+        let key = if self.canonical {
+            kmer.canonical()
+        } else {
+            kmer.clone()
+        };
+        self.counts.get(&key).cloned().unwrap_or(0)
+    }
+
+    /// The length of the counted k-mers.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Whether a k-mer and its reverse complement are counted together.
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// The number of distinct k-mers (or canonical k-mers).
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Keep only the k-mers for which `f` returns true, e.g. to drop k-mers seen only once.
+    pub fn retain<F: FnMut(&Kmer, u64) -> bool>(&mut self, mut f: F) {
+        self.counts.retain(|kmer, &mut count| f(kmer, count));
+    }
+
+    /// Release unused capacity of the table and of the buffers of the stored k-mers, e.g. after
+    /// `retain` removed many entries. The keys have to be moved out of the table to shrink them,
+    /// so the table is rebuilt.
+    pub fn shrink_to_fit(&mut self) {
+        let counts = mem::take(&mut self.counts);
+        self.counts = counts
+            .into_iter()
+            .map(|(mut kmer, count)| {
+                kmer.sequence.shrink_to_fit();
+                (kmer, count)
+            })
+            .collect();
+        self.counts.shrink_to_fit();
+    }
+
+    /// Estimate the number of bytes used by the counter: the table slots for its current
+    /// capacity (a k-mer, a count and one byte of bookkeeping each) plus the heap buffers of
+    /// the stored k-mers.
+    pub fn memory_usage(&self) -> usize {
+        let slot = mem::size_of::<Kmer>() + mem::size_of::<u64>() + 1;
+        let kmers: usize = self
+            .counts
+            .keys()
+            .map(|kmer| kmer.sequence.capacity())
+            .sum();
+        mem::size_of::<Self>() + self.counts.capacity() * slot + kmers
+    }
+
+    /// Iterate over the counted k-mers and their counts, in arbitrary order. If the counter is
+    /// canonical, each k-mer is reported in its canonical form.
+    pub fn iter(&self) -> impl Iterator<Item = (&Kmer, u64)> {
+        self.counts.iter().map(|(kmer, &count)| (kmer, count))
+    }
+
+    /// Return the `n` most frequent k-mers with their counts, sorted by decreasing count. K-mers
+    /// with equal counts are sorted by increasing k-mer (see `Kmer`'s `Ord`), such that the
+    /// result is deterministic. Only `n` k-mers are kept in a heap while scanning the table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::counter::KmerCounter;
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let counter = KmerCounter::from_sequence(b"AAAACAC", 2, false);
+    /// assert_eq!(
+    ///     counter.top_k(2),
+    ///     vec![(Kmer::from_literal("AA"), 3), (Kmer::from_literal("AC"), 2)]
+    /// );
+    /// ```
+    pub fn top_k(&self, n: usize) -> Vec<(Kmer, u64)> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(n + 1);
+        for (kmer, &count) in &self.counts {
+            heap.push(Reverse((count, Reverse(kmer))));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((count, Reverse(kmer)))| (kmer.clone(), count))
+            .collect()
+    }
+}
+
+/// Largest k supported by `U64KmerCounter`.
+pub const MAX_U64_K: usize = 32;
+
+/// A canonical k-mer counter for `k <= MAX_U64_K`, keyed by the k-mers packed into a `u64` (see
+/// `Kmer::to_u64`). This avoids allocating and hashing a `Kmer` per window, and is therefore
+/// considerably faster and smaller than a canonical `KmerCounter`, into which it can be
+/// converted.
+///
+/// A canonical k-mer is keyed by the smaller of its own packed value and that of its reverse
+/// complement.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::counter::U64KmerCounter;
+/// use bio::data_structures::kmer::Kmer;
+///
+/// let counter = U64KmerCounter::from_sequence(b"ACGTTAC", 3).unwrap();
+/// // ACG occurs once, CGT is its reverse complement
+/// assert_eq!(counter.get(&Kmer::from_literal("ACG")), 2);
+/// assert!(U64KmerCounter::new(33).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct U64KmerCounter {
+    k: usize,
+    counts: HashMap<u64, u64>,
+}
+
+impl U64KmerCounter {
+    /// Create an empty counter of canonical k-mers of length `k`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::KTooLarge` if `k > MAX_U64_K`.
+    pub fn new(k: usize) -> Result<Self> {
+        if k > MAX_U64_K {
+            return Err(Error::KTooLarge { k, max: MAX_U64_K });
+        }
+        Ok(U64KmerCounter {
+            k,
+            counts: HashMap::new(),
+        })
+    }
+
+    /// Count the canonical k-mers of `seq`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::KTooLarge` if `k > MAX_U64_K`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn from_sequence(seq: &[u8], k: usize) -> Result<Self> {
+        let mut counter = U64KmerCounter::new(k)?;
+        counter.add_sequence(seq);
+        Ok(counter)
+    }
+
+    /// Add the canonical k-mers of `seq` to the counts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn add_sequence(&mut self, seq: &[u8]) {
+        let k = self.k;
+        if k == 0 {
+            return;
+        }
+        let mask = u64::MAX >> (64 - 2 * k);
+        let mut forward = 0u64;
+        let mut revcomp = 0u64;
+        for (i, &nuc) in seq.iter().enumerate() {
+            let code = u64::from(nuc_to_byte(char::from(nuc)));
+            // the first base of the window occupies the lowest bits, as in `Kmer`, so the
+            // complement of the new base becomes the first base of the reverse complement
+            forward = (forward >> 2) | (code << (2 * (k - 1)));
+            revcomp = ((revcomp << 2) | (3 - code)) & mask;
+            if i + 1 >= k {
+                *self.counts.entry(forward.min(revcomp)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// The number of occurrences of `kmer` together with its reverse complement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `kmer` has a different length than the counted k-mers.
+    pub fn get(&self, kmer: &Kmer) -> u64 {
+        assert_eq!(kmer.k, self.k, "k-mer length differs from the counter");
+        let key = canonical_u64(kmer.to_u64().unwrap(), self.k);
+        self.counts.get(&key).cloned().unwrap_or(0)
+    }
+
+    /// The length of the counted k-mers.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The number of distinct canonical k-mers.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+impl From<U64KmerCounter> for KmerCounter {
+    fn from(counter: U64KmerCounter) -> Self {
+        let k = counter.k;
+        KmerCounter {
+            k,
+            canonical: true,
+            counts: counter
+                .counts
+                .into_iter()
+                .map(|(packed, count)| (Kmer::from_u64(k, packed).canonical(), count))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<KmerCounter> for U64KmerCounter {
+    type Error = Error;
+
+    /// Convert a `KmerCounter` for `k <= MAX_U64_K`. If the counter is not canonical, the counts
+    /// of each k-mer and its reverse complement are summed.
+    fn try_from(counter: KmerCounter) -> Result<Self> {
+        let mut converted = U64KmerCounter::new(counter.k)?;
+        for (kmer, count) in counter.counts {
+            let key = canonical_u64(kmer.to_u64().unwrap(), counter.k);
+            *converted.counts.entry(key).or_insert(0) += count;
+        }
+        Ok(converted)
+    }
+}
+
+/// The canonical key of a k-mer packed into a `u64`, i.e. the smaller of the packed value and
+/// that of the reverse complement.
+fn canonical_u64(packed: u64, k: usize) -> u64 {
+    let revcomp = (0..k).fold(0, |revcomp, i| (revcomp << 2) | (3 - ((packed >> (2 * i)) & 3)));
+    packed.min(revcomp)
+}
+
+/// Jaccard similarity of the distinct k-mers of `a` and `b`, i.e. the size of their intersection
+/// divided by the size of their union, regardless of the counts. Two empty counters have a
+/// similarity of 1, like two empty sketches.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::counter::{jaccard, KmerCounter};
+///
+/// let a = KmerCounter::from_sequence(b"ACGTT", 2, false);
+/// let b = KmerCounter::from_sequence(b"CGTTA", 2, false);
+/// // CG, GT and TT are shared, AC and TA are not
+/// assert_eq!(jaccard(&a, &b), 3.0 / 5.0);
+/// ```
+pub fn jaccard(a: &KmerCounter, b: &KmerCounter) -> f64 {
+    let shared = shared_kmers(a, b);
+    let union = a.len() + b.len() - shared;
+    if union == 0 {
+        1.0
+    } else {
+        shared as f64 / union as f64
+    }
+}
+
+/// Fraction of the distinct k-mers of `a` that also occur in `b`, regardless of the counts.
+/// This is 0 if `a` is empty.
+pub fn containment(a: &KmerCounter, b: &KmerCounter) -> f64 {
+    if a.is_empty() {
+        0.0
+    } else {
+        shared_kmers(a, b) as f64 / a.len() as f64
+    }
+}
+
+/// The number of distinct k-mers counted by both `a` and `b`.
+fn shared_kmers(a: &KmerCounter, b: &KmerCounter) -> usize {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small
+        .counts
+        .keys()
+        .filter(|kmer| large.counts.contains_key(kmer))
+        .count()
+}
+
+/// Count the (possibly overlapping) occurrences of `query` in `seq`. If `canonical` is true,
+/// occurrences of its reverse complement are counted as well.
+///
+/// # Panics
+///
+/// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::counter::count_occurrences;
+/// use bio::data_structures::kmer::Kmer;
+///
+/// assert_eq!(count_occurrences(b"AAAA", &Kmer::from_literal("AA"), false), 3);
+/// assert_eq!(count_occurrences(b"AATT", &Kmer::from_literal("AA"), true), 2);
+/// ```
+pub fn count_occurrences(seq: &[u8], query: &Kmer, canonical: bool) -> usize {
+    if query.k == 0 {
+        return 0;
+    }
+    let revcomp = query.make_reverse_complement();
+    seq.windows(query.k)
+        .map(|window| Kmer::new(query.k, window))
+        .filter(|kmer| kmer == query || (canonical && *kmer == revcomp))
+        .count()
+}
+
+/// Find the start positions of the windows of `seq` within Hamming distance `max_mismatches` of
+/// `query`, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::counter::find_approximate;
+/// use bio::data_structures::kmer::Kmer;
+///
+/// let query = Kmer::from_literal("TACA");
+/// assert_eq!(find_approximate(b"GATTACAGATGACA", &query, 1), vec![3, 10]);
+/// ```
+pub fn find_approximate(seq: &[u8], query: &Kmer, max_mismatches: usize) -> Vec<usize> {
+    if query.k == 0 {
+        return Vec::new();
+    }
+    seq.windows(query.k)
+        .enumerate()
+        .filter(|(_, window)| Kmer::new(query.k, window).hamming_distance(query) <= max_mismatches)
+        .map(|(start, _)| start)
+        .collect()
+}
+
+/// Whether `kmer` occurs exactly once in `seq`, considering both strands. The empty k-mer is
+/// never unique.
+///
+/// # Panics
+///
+/// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+pub fn is_unique_in(seq: &[u8], kmer: &Kmer) -> bool {
+    if kmer.k == 0 {
+        return false;
+    }
+    let canonical = kmer.canonical();
+    seq.windows(kmer.k)
+        .filter(|window| Kmer::new(kmer.k, window).canonical() == canonical)
+        .take(2)
+        .count()
+        == 1
+}
+
+/// Compute a mappability track of `seq`: for each position starting a k-mer, whether that k-mer
+/// occurs exactly once in `seq`, considering both strands.
+///
+/// # Panics
+///
+/// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::counter::mappability_track;
+///
+/// // ACG occurs twice, and its reverse complement CGT once
+/// assert_eq!(
+///     mappability_track(b"ACGACGT", 3),
+///     vec![false, true, true, false, false]
+/// );
+/// ```
+pub fn mappability_track(seq: &[u8], k: usize) -> Vec<bool> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let counts = KmerCounter::from_sequence(seq, k, true);
+    seq.windows(k)
+        .map(|window| counts.get(&Kmer::new(k, window)) == 1)
+        .collect()
+}
+
+/// Expected number of shared k-mers between two random sequences of lengths `len_a` and `len_b`
+/// whose bases are drawn independently with GC content `gc`, i.e. the expected number of pairs
+/// of positions at which both sequences have the same k-mer.
+///
+/// Two random bases are equal with probability `p = 2 (gc / 2)^2 + 2 ((1 - gc) / 2)^2`, hence
+/// the expectation is `(len_a - k + 1) (len_b - k + 1) p^k`. Pairs of overlapping windows are
+/// not independent, but this does not affect the expectation.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::counter::expected_shared_kmers;
+///
+/// // 3 * 3 pairs of 2-mers, each matching with probability (1 / 4)^2
+/// assert_eq!(expected_shared_kmers(4, 4, 2, 0.5), 9.0 / 16.0);
+/// ```
+pub fn expected_shared_kmers(len_a: usize, len_b: usize, k: usize, gc: f64) -> f64 {
+    if k == 0 || len_a < k || len_b < k {
+        return 0.0;
+    }
+    let p = 2.0 * (gc / 2.0).powi(2) + 2.0 * ((1.0 - gc) / 2.0).powi(2);
+    (len_a - k + 1) as f64 * (len_b - k + 1) as f64 * p.powi(k as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabets::dna;
+    use crate::data_structures::test_utils::random_seq;
+
+    fn distinct_kmers_hashset(seq: &[u8], k: usize) -> u64 {
+        seq.windows(k)
+            .map(|window| Kmer::new(k, window))
+            .collect::<HashSet<Kmer>>()
+            .len() as u64
+    }
+
+    #[test]
+    fn test_distinct_kmers_bitset() {
+        let seq = b"ATGCGTACGTTAGCATCGGATCGATCGTAGCTAGCATGCATCGATCGGCTAGCGCATTAAAAAAA";
+        assert_eq!(
+            distinct_kmers_bitset(seq, 3),
+            distinct_kmers_hashset(seq, 3)
+        );
+        assert_eq!(distinct_kmers_bitset(seq, 1), 4);
+        assert_eq!(distinct_kmers_bitset(b"AAAAAAA", 3), 1);
+        assert_eq!(distinct_kmers_bitset(b"AC", 3), 0);
+        assert_eq!(
+            distinct_kmers_bitset(seq, MAX_BITSET_K + 1),
+            distinct_kmers_hashset(seq, MAX_BITSET_K + 1)
+        );
+    }
+
+    #[test]
+    fn test_mappability() {
+        let repeat = b"GATTACAGG";
+        let seq = [&b"CCTAGCGTTCA"[..], repeat, b"TCAGCGCATC", repeat, b"TGCA"].concat();
+        let k = 5;
+        let track = mappability_track(&seq, k);
+        assert_eq!(track.len(), seq.len() - k + 1);
+
+        for (start, &unique) in track.iter().enumerate() {
+            let kmer = Kmer::new(k, &seq[start..start + k]);
+            assert_eq!(unique, is_unique_in(&seq, &kmer));
+        }
+        // k-mers lying within one of the repeat copies are not unique
+        for copy in &[11, 30] {
+            for start in *copy..copy + repeat.len() - k + 1 {
+                assert!(!track[start]);
+            }
+        }
+        assert!(track[0]);
+
+        // the reverse complement counts as another occurrence
+        assert!(!is_unique_in(b"ACCGTTTTCGGT", &Kmer::from_literal("ACCG")));
+        assert!(is_unique_in(b"ACCGTTTTCGGA", &Kmer::from_literal("ACCG")));
+        assert!(!is_unique_in(b"ACGT", &Kmer::from_literal("")));
+        assert!(!is_unique_in(b"", &Kmer::from_literal("")));
+    }
+
+    #[test]
+    fn test_kmer_counter() {
+        let seq = b"ATGCGTACGTTAGCATCGGATCGATCGTAGCTAGCATGCATCGATCGGCTAGCGCATTAAAAAAA";
+        for &canonical in &[false, true] {
+            for k in 1..8 {
+                let mut naive: HashMap<Kmer, u64> = HashMap::new();
+                for window in seq.windows(k) {
+                    let kmer = Kmer::new(k, window);
+                    let key = if canonical { kmer.canonical() } else { kmer };
+                    *naive.entry(key).or_insert(0) += 1;
+                }
+                let counter = KmerCounter::from_sequence(seq, k, canonical);
+                assert_eq!(counter.len(), naive.len());
+                for (kmer, count) in counter.iter() {
+                    assert_eq!(naive[kmer], count);
+                }
+            }
+        }
+
+        let mut counter = KmerCounter::from_sequence(b"AAAA", 2, true);
+        assert_eq!(counter.get(&Kmer::from_literal("AA")), 3);
+        assert_eq!(counter.get(&Kmer::from_literal("TT")), 3);
+        counter.add_sequence(b"T");
+        counter.add_sequence(b"TTC");
+        assert_eq!(counter.get(&Kmer::from_literal("AA")), 4);
+        assert_eq!(counter.get(&Kmer::from_literal("GA")), 1);
+        assert_eq!(counter.len(), 2);
+
+        // soft-masked and RNA input is counted like upper case DNA
+        let dna = KmerCounter::from_sequence(b"GATTACAGCT", 3, true);
+        assert_eq!(KmerCounter::from_sequence(b"gattACAgct", 3, true), dna);
+        assert_eq!(KmerCounter::from_sequence(b"GAUUACAGCU", 3, true), dna);
+
+        let forward = KmerCounter::from_sequence(b"AAAA", 2, false);
+        assert_eq!(forward.get(&Kmer::from_literal("AA")), 3);
+        assert_eq!(forward.get(&Kmer::from_literal("TT")), 0);
+        assert_eq!(forward.len(), 1);
+        assert_eq!(
+            forward.iter().collect::<Vec<_>>(),
+            vec![(&Kmer::from_literal("AA"), 3)]
+        );
+    }
+
+    #[test]
+    fn test_top_k() {
+        let counter = KmerCounter::from_sequence(b"GATTACATTACATTAGG", 3, false);
+        let kmer = |seq| Kmer::from_literal(seq);
+        assert_eq!(
+            counter.top_k(4),
+            vec![
+                (kmer("ATT"), 3),
+                (kmer("TTA"), 3),
+                (kmer("ACA"), 2),
+                (kmer("CAT"), 2)
+            ]
+        );
+        assert!(counter.top_k(0).is_empty());
+        let all = counter.top_k(100);
+        assert_eq!(all.len(), counter.len());
+        assert!(all
+            .windows(2)
+            .all(|pair| (Reverse(pair[0].1), &pair[0].0) < (Reverse(pair[1].1), &pair[1].0)));
+    }
+
+    #[test]
+    fn test_from_sequence_parallel() {
+        let seq = random_seq(200_000, 7);
+        for &canonical in &[false, true] {
+            let serial = KmerCounter::from_sequence(&seq, 5, canonical);
+            for &chunks in &[1, 2, 3, 7] {
+                let parallel = KmerCounter::from_sequence_chunked(&seq, 5, canonical, chunks);
+                assert_eq!(parallel, serial);
+            }
+            assert_eq!(
+                KmerCounter::from_sequence_parallel(&seq, 5, canonical),
+                serial
+            );
+        }
+        assert!(KmerCounter::from_sequence_parallel(b"ACG", 4, false).is_empty());
+        assert_eq!(
+            KmerCounter::from_sequence_parallel(b"ACGT", 2, false),
+            KmerCounter::from_sequence(b"ACGT", 2, false)
+        );
+    }
+
+    #[test]
+    fn test_jaccard_containment() {
+        let a = KmerCounter::from_sequence(b"AAAACGT", 3, false);
+        let b = KmerCounter::from_sequence(b"ACGTTTTTTTT", 3, false);
+        // a: AAA, AAC, ACG, CGT; b: ACG, CGT, GTT, TTT
+        assert_eq!(jaccard(&a, &b), 2.0 / 6.0);
+        assert_eq!(jaccard(&b, &a), 2.0 / 6.0);
+        assert_eq!(containment(&a, &b), 0.5);
+        assert_eq!(containment(&b, &a), 0.5);
+
+        let sub = KmerCounter::from_sequence(b"AAACG", 3, false);
+        assert_eq!(containment(&sub, &a), 1.0);
+        assert_eq!(containment(&a, &sub), 0.75);
+        assert_eq!(jaccard(&a, &a), 1.0);
+
+        let disjoint = KmerCounter::from_sequence(b"GGGGG", 3, false);
+        assert_eq!(jaccard(&a, &disjoint), 0.0);
+        assert_eq!(containment(&a, &disjoint), 0.0);
+
+        let empty = KmerCounter::new(3, false);
+        assert_eq!(jaccard(&empty, &empty), 1.0);
+        assert_eq!(jaccard(&empty, &a), 0.0);
+        assert_eq!(containment(&empty, &a), 0.0);
+    }
+
+    #[test]
+    fn test_count_occurrences() {
+        let kmer = |seq| Kmer::from_literal(seq);
+        assert_eq!(count_occurrences(b"AAAA", &kmer("AA"), false), 3);
+        assert_eq!(count_occurrences(b"AAAA", &kmer("AA"), true), 3);
+        assert_eq!(count_occurrences(b"GATTACATTT", &kmer("AAT"), false), 0);
+        assert_eq!(count_occurrences(b"GATTACATTT", &kmer("AAT"), true), 2);
+        // a palindrome is counted once per occurrence
+        assert_eq!(count_occurrences(b"ACGTACGT", &kmer("ACGT"), true), 2);
+        assert_eq!(count_occurrences(b"AC", &kmer("ACG"), true), 0);
+        assert_eq!(count_occurrences(b"ACGT", &kmer(""), false), 0);
+    }
+
+    #[test]
+    fn test_find_approximate() {
+        let seq = b"GATTACAGATGACA";
+        let query = Kmer::from_literal("TACA");
+        assert_eq!(find_approximate(seq, &query, 0), vec![3]);
+        // GACA at 10 is the only window with a single mismatch
+        assert_eq!(find_approximate(seq, &query, 1), vec![3, 10]);
+        assert_eq!(find_approximate(seq, &query, 4).len(), seq.len() - 3);
+        assert!(find_approximate(b"TAC", &query, 4).is_empty());
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let seq: Vec<u8> = (0..20_000u32)
+            .map(|i| b"ACGT"[(i.wrapping_mul(2_654_435_761) >> 30) as usize])
+            .collect();
+        let mut counter = KmerCounter::from_sequence(&seq, 15, true);
+        let distinct = counter.len();
+        let before = counter.memory_usage();
+        assert!(before > distinct * 8);
+
+        let mut kept = 0;
+        counter.retain(|_, _| {
+            kept += 1;
+            kept % 100 == 0
+        });
+        assert_eq!(counter.len(), distinct / 100);
+        let retained = counter.memory_usage();
+        counter.shrink_to_fit();
+        let shrunk = counter.memory_usage();
+        assert!(shrunk < retained);
+        assert!(shrunk * 10 < before);
+        assert!(counter
+            .iter()
+            .all(|(kmer, _)| kmer.sequence.capacity() == kmer.sequence.len()));
+        assert_eq!(counter.len(), distinct / 100);
+    }
+
+    #[test]
+    fn test_u64_kmer_counter() {
+        let repeat = b"GATTACAGGCATCAGCGTTCAGCATTAGC";
+        let seq = [
+            &b"CCTAGCGTTCATTGACCATGCAGGTACCAGTA"[..],
+            repeat,
+            b"TCAGCGCATCTTTTTTTTTTTTTTTTTTTTTTTTTTAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            &dna::revcomp(&repeat[..]),
+            repeat,
+        ]
+        .concat();
+        let k = 21;
+
+        let counter = KmerCounter::from_sequence(&seq, k, true);
+        let u64_counter = U64KmerCounter::from_sequence(&seq, k).unwrap();
+        assert_eq!(u64_counter.len(), counter.len());
+        for (kmer, count) in counter.iter() {
+            assert_eq!(u64_counter.get(kmer), count);
+            let revcomp = Kmer::new(k, &dna::revcomp(kmer.decode().as_bytes()));
+            assert_eq!(u64_counter.get(&revcomp), count);
+        }
+        let repeated = Kmer::new(k, &repeat[..k]);
+        assert_eq!(u64_counter.get(&repeated), 3);
+
+        assert_eq!(KmerCounter::from(u64_counter.clone()), counter);
+        assert_eq!(U64KmerCounter::try_from(counter).unwrap(), u64_counter);
+        assert_eq!(
+            U64KmerCounter::try_from(KmerCounter::from_sequence(&seq, k, false)).unwrap(),
+            u64_counter
+        );
+
+        assert_eq!(
+            U64KmerCounter::from_sequence(&seq, 32).unwrap().len(),
+            KmerCounter::from_sequence(&seq, 32, true).len()
+        );
+        assert_eq!(
+            U64KmerCounter::new(33),
+            Err(Error::KTooLarge { k: 33, max: 32 })
+        );
+        assert!(U64KmerCounter::try_from(KmerCounter::new(33, true)).is_err());
+    }
+
+    #[test]
+    fn test_expected_shared_kmers() {
+        assert_eq!(expected_shared_kmers(4, 4, 2, 0.5), 0.5625);
+        // only G and C, each with probability 1 / 2
+        assert_eq!(expected_shared_kmers(5, 3, 2, 1.0), 4.0 * 2.0 * 0.25);
+        // p = 2 * 0.05^2 + 2 * 0.45^2 = 0.41
+        assert!((expected_shared_kmers(3, 3, 1, 0.1) - 9.0 * 0.41).abs() < 1e-12);
+        assert_eq!(expected_shared_kmers(3, 10, 4, 0.5), 0.0);
+    }
+}
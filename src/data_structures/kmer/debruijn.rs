@@ -0,0 +1,506 @@
+//! Helpers for walking and building de Bruijn graphs of k-mers.
+
+use std::collections::{HashSet, VecDeque};
+
+use bio_types::strand::Strand;
+
+use crate::data_structures::kmer::{byte_to_nuc, Kmer};
+
+/// A walker along a path of k-mers, e.g. through a de Bruijn graph. Each step appends a base to
+/// the current k-mer (dropping its first base). A bounded history of the previous k-mers allows
+/// to backtrack.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::debruijn::KmerWalker;
+/// use bio::data_structures::kmer::Kmer;
+///
+/// let mut walker = KmerWalker::new(Kmer::from_literal("ACG"), 10);
+/// assert_eq!(walker.step(b'T'), Kmer::from_literal("ACG"));
+/// assert_eq!(walker.current(), &Kmer::from_literal("CGT"));
+/// assert!(walker.backtrack());
+/// assert_eq!(walker.current(), &Kmer::from_literal("ACG"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct KmerWalker {
+    current: Kmer,
+    history: VecDeque<Kmer>,
+    max_history: usize,
+}
+
+impl KmerWalker {
+    /// Start a walk at `start`, remembering at most `max_history` previous k-mers.
+    pub fn new(start: Kmer, max_history: usize) -> Self {
+        KmerWalker {
+            current: start,
+            history: VecDeque::with_capacity(max_history),
+            max_history,
+        }
+    }
+
+    /// The current k-mer.
+    pub fn current(&self) -> &Kmer {
+        &self.current
+    }
+
+    /// The remembered previous k-mers, from the oldest to the most recent one.
+    pub fn history(&self) -> impl Iterator<Item = &Kmer> {
+        self.history.iter()
+    }
+
+    /// Advance the walk by appending `base` to the current k-mer. Returns the previous k-mer,
+    /// which is also recorded in the history.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a nucleotide accepted by [`Kmer::new`].
+    pub fn step(&mut self, base: u8) -> Kmer {
+        let previous = self.current.clone();
+        self.current.push_back(base);
+        if self.max_history > 0 {
+            if self.history.len() == self.max_history {
+                self.history.pop_front();
+            }
+            self.history.push_back(previous.clone());
+        }
+        previous
+    }
+
+    /// Undo the last step by restoring the most recent k-mer of the history. Returns `false` if
+    /// the history is exhausted.
+    pub fn backtrack(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some(previous) => {
+                self.current = previous;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A de Bruijn graph of the k-mers of double-stranded DNA sequences. A k-mer and its reverse
+/// complement are the same node, stored in canonical form, and an edge connects two nodes if
+/// one of the strands of the first overlaps one of the strands of the second by `k - 1` bases.
+/// Each edge therefore carries the orientation in which its target node is entered, see
+/// `DeBruijnGraph::out_edges`.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::debruijn::DeBruijnGraph;
+///
+/// // the second sequence is the reverse complement of the end of the first one
+/// let graph = DeBruijnGraph::from_sequences(&[b"GATTACAGG", b"CCTGTAA"], 4);
+/// let unitigs = graph.unitigs();
+/// assert_eq!(unitigs.len(), 1);
+/// assert!(unitigs[0] == b"GATTACAGG" || unitigs[0] == b"CCTGTAATC");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeBruijnGraph {
+    k: usize,
+    kmers: HashSet<Kmer>,
+}
+
+impl DeBruijnGraph {
+    /// Build the graph of the k-mers of `seqs`, each sequence being read on both strands.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero or a sequence contains a character that [`Kmer::new`] rejects.
+    pub fn from_sequences(seqs: &[&[u8]], k: usize) -> Self {
+        assert!(k > 0, "k must be positive");
+        let kmers = seqs
+            .iter()
+            .flat_map(|seq| seq.windows(k))
+            .map(|window| Kmer::new(k, window).canonical())
+            .collect();
+        DeBruijnGraph { k, kmers }
+    }
+
+    /// The length of the k-mers of the graph.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The number of nodes, i.e. of distinct canonical k-mers.
+    pub fn len(&self) -> usize {
+        self.kmers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kmers.is_empty()
+    }
+
+    /// Whether `kmer` or its reverse complement is a node of the graph.
+    pub fn contains(&self, kmer: &Kmer) -> bool {
+        self.kmers.contains(&kmer.canonical())
+    }
+
+    /// The edges leaving `kmer` when reading the strand given by `kmer` itself, i.e. the nodes
+    /// whose forward or reverse strand follows `kmer`. Each target node is returned in canonical
+    /// form together with the strand by which it is entered: `Strand::Forward` if its canonical
+    /// form follows `kmer`, `Strand::Reverse` if its reverse complement does, and
+    /// `Strand::Unknown` if it is its own reverse complement.
+    pub fn out_edges(&self, kmer: &Kmer) -> Vec<(Kmer, Strand)> {
+        self.oriented_successors(kmer)
+            .into_iter()
+            .map(|mut node| {
+                let strand = node.make_canonical();
+                (node, strand)
+            })
+            .collect()
+    }
+
+    /// The edges entering `kmer` when reading the strand given by `kmer`, see `out_edges`.
+    pub fn in_edges(&self, kmer: &Kmer) -> Vec<(Kmer, Strand)> {
+        self.oriented_predecessors(kmer)
+            .into_iter()
+            .map(|mut node| {
+                let strand = node.make_canonical();
+                (node, strand)
+            })
+            .collect()
+    }
+
+    /// Compact the graph into unitigs, i.e. maximal paths whose inner nodes have a single
+    /// incoming and a single outgoing edge. Every node is part of exactly one unitig, so each
+    /// sequence of a double-stranded input is reported once, in one of its two orientations.
+    /// The unitigs are returned in arbitrary order.
+    pub fn unitigs(&self) -> Vec<Vec<u8>> {
+        let mut visited = HashSet::new();
+        let mut unitigs = Vec::new();
+        for start in &self.kmers {
+            if !visited.insert(start.clone()) {
+                continue;
+            }
+            let mut right = Vec::new();
+            let mut current = start.clone();
+            while let Some(next) = self.extension(&current, &visited, true) {
+                right.push(byte_to_nuc(next.index(self.k - 1)) as u8);
+                visited.insert(next.canonical());
+                current = next;
+            }
+            let mut left = Vec::new();
+            let mut current = start.clone();
+            while let Some(previous) = self.extension(&current, &visited, false) {
+                left.push(byte_to_nuc(previous.index(0)) as u8);
+                visited.insert(previous.canonical());
+                current = previous;
+            }
+            left.reverse();
+            left.extend(start.decode().bytes());
+            left.extend(right);
+            unitigs.push(left);
+        }
+        unitigs
+    }
+
+    /// The unique unvisited neighbour of the oriented `kmer` to the right (or left), if `kmer`
+    /// is its only neighbour on the opposite side.
+    fn extension(&self, kmer: &Kmer, visited: &HashSet<Kmer>, right: bool) -> Option<Kmer> {
+        let neighbours = |kmer: &Kmer, right: bool| {
+            if right {
+                self.oriented_successors(kmer)
+            } else {
+                self.oriented_predecessors(kmer)
+            }
+        };
+        let forward = neighbours(kmer, right);
+        if forward.len() != 1 {
+            return None;
+        }
+        let next = forward.into_iter().next().unwrap();
+        if neighbours(&next, !right).len() != 1 || visited.contains(&next.canonical()) {
+            return None;
+        }
+        Some(next)
+    }
+
+    fn oriented_successors(&self, kmer: &Kmer) -> Vec<Kmer> {
+        kmer.successors()
+            .iter()
+            .filter(|next| self.contains(next))
+            .cloned()
+            .collect()
+    }
+
+    fn oriented_predecessors(&self, kmer: &Kmer) -> Vec<Kmer> {
+        kmer.predecessors()
+            .iter()
+            .filter(|previous| self.contains(previous))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A single-stranded de Bruijn graph as used for assembly: the nodes are (k-1)-mers and each
+/// k-mer is an edge from its prefix to its suffix. Unlike `DeBruijnGraph`, a k-mer and its
+/// reverse complement are distinct edges.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::debruijn::EdgeCentricDeBruijnGraph;
+/// use bio::data_structures::kmer::Kmer;
+///
+/// let kmers = ["ACG", "CGT", "CGA"].iter().map(|&kmer| Kmer::from_literal(kmer));
+/// let graph = EdgeCentricDeBruijnGraph::from_kmers(kmers);
+/// assert_eq!(graph.nodes().count(), 4);
+/// assert_eq!(
+///     graph.successors(&Kmer::from_literal("CG")),
+///     vec![Kmer::from_literal("GA"), Kmer::from_literal("GT")]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgeCentricDeBruijnGraph {
+    nodes: HashSet<Kmer>,
+    edges: HashSet<Kmer>,
+}
+
+impl EdgeCentricDeBruijnGraph {
+    /// Build the graph whose edges are `kmers`. Repeated k-mers yield a single edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a k-mer is empty or the k-mers are not all of the same length.
+    pub fn from_kmers<I: IntoIterator<Item = Kmer>>(kmers: I) -> Self {
+        let mut graph = EdgeCentricDeBruijnGraph {
+            nodes: HashSet::new(),
+            edges: HashSet::new(),
+        };
+        for kmer in kmers {
+            if let Some(edge) = graph.edges.iter().next() {
+                assert_eq!(edge.k, kmer.k, "all k-mers must have the same length");
+            }
+            graph.nodes.insert(kmer.prefix());
+            graph.nodes.insert(kmer.suffix());
+            graph.edges.insert(kmer);
+        }
+        graph
+    }
+
+    /// The (k-1)-mers of the graph, in arbitrary order.
+    pub fn nodes(&self) -> impl Iterator<Item = &Kmer> {
+        self.nodes.iter()
+    }
+
+    /// The k-mers of the graph, in arbitrary order. Each edge leads from the prefix to the
+    /// suffix of its k-mer.
+    pub fn edges(&self) -> impl Iterator<Item = &Kmer> {
+        self.edges.iter()
+    }
+
+    /// The nodes reached from `node` by an edge, ordered by the appended base (`A`, `C`, `G`,
+    /// `T`).
+    pub fn successors(&self, node: &Kmer) -> Vec<Kmer> {
+        b"ACGT"
+            .iter()
+            .map(|&base| {
+                let mut edge = node.clone();
+                edge.push(base);
+                edge
+            })
+            .filter(|edge| self.edges.contains(edge))
+            .map(|edge| edge.suffix())
+            .collect()
+    }
+}
+
+/// Generate a cyclic de Bruijn sequence of order `k` over the alphabet `ACGT`, i.e. a sequence
+/// of length 4^k in which every k-mer occurs exactly once when the sequence is read cyclically.
+/// The sequence is the concatenation of the Lyndon words whose length divides `k`, in
+/// lexicographic order (the FKM algorithm), and hence is the lexicographically smallest de
+/// Bruijn sequence.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::debruijn::debruijn_sequence;
+///
+/// assert_eq!(debruijn_sequence(1), b"ACGT");
+/// assert_eq!(debruijn_sequence(2), b"AACAGATCCGCTGGTT");
+/// ```
+pub fn debruijn_sequence(k: usize) -> Vec<u8> {
+    fn lyndon_words(t: usize, p: usize, k: usize, word: &mut [usize], seq: &mut Vec<u8>) {
+        if t > k {
+            if k.is_multiple_of(p) {
+                seq.extend(word[1..=p].iter().map(|&base| b"ACGT"[base]));
+            }
+        } else {
+            word[t] = word[t - p];
+            lyndon_words(t + 1, p, k, word, seq);
+            for base in word[t - p] + 1..4 {
+                word[t] = base;
+                lyndon_words(t + 1, t, k, word, seq);
+            }
+        }
+    }
+
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut seq = Vec::with_capacity(1 << (2 * k));
+    lyndon_words(1, 1, k, &mut vec![0; k + 1], &mut seq);
+    seq
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabets::dna;
+
+    #[test]
+    fn test_kmer_walker() {
+        let seq = b"ACGTTGCATGTCGCATGA";
+        let k = 5;
+        let mut walker = KmerWalker::new(Kmer::new(k, &seq[..k]), 3);
+        for (i, &base) in seq[k..].iter().enumerate() {
+            assert_eq!(walker.step(base), Kmer::new(k, &seq[i..i + k]));
+            assert_eq!(walker.current(), &Kmer::new(k, &seq[i + 1..i + 1 + k]));
+        }
+        assert_eq!(walker.history().count(), 3);
+
+        let end = seq.len() - k;
+        for back in 1..=3 {
+            assert!(walker.backtrack());
+            assert_eq!(
+                walker.current(),
+                &Kmer::new(k, &seq[end - back..end - back + k])
+            );
+        }
+        assert!(!walker.backtrack());
+    }
+
+    #[test]
+    fn test_unitigs_of_both_strands() {
+        let seq = b"ATGCGTACGTTAGCATCGGATCGATCGTAGCTAGCATGCATCGATCGGCTAGCGCATTA";
+        let revcomp = dna::revcomp(&seq[..]);
+        let k = 11;
+
+        let graph = DeBruijnGraph::from_sequences(&[seq, &revcomp], k);
+        assert_eq!(graph.len(), seq.len() - k + 1);
+        let unitigs = graph.unitigs();
+        assert_eq!(unitigs.len(), 1);
+        assert!(unitigs[0] == seq.to_vec() || unitigs[0] == revcomp);
+
+        // a forward read of the first half and a reverse read of the second half overlapping it
+        let half = seq.len() / 2;
+        let reads = [&seq[..half + k], &revcomp[..seq.len() - half]];
+        let unitigs = DeBruijnGraph::from_sequences(&reads, k).unitigs();
+        assert_eq!(unitigs.len(), 1);
+        assert!(unitigs[0] == seq.to_vec() || unitigs[0] == revcomp);
+    }
+
+    #[test]
+    fn test_unitigs_branching() {
+        let k = 7;
+        let shared = b"GATTACAGGCT";
+        let a = [&b"TTGCCACT"[..], shared, b"CCATGTAC"].concat();
+        let b = [&b"AGGTCTTG"[..], shared, b"TGTCCGAG"].concat();
+        let graph = DeBruijnGraph::from_sequences(&[&a, &b], k);
+        let unitigs = graph.unitigs();
+
+        // each node occurs in exactly one unitig
+        let mut kmers: Vec<Kmer> = unitigs
+            .iter()
+            .flat_map(|unitig| unitig.windows(k).map(|w| Kmer::new(k, w).canonical()))
+            .collect();
+        assert_eq!(kmers.len(), graph.len());
+        kmers.sort();
+        kmers.dedup();
+        assert_eq!(kmers.len(), graph.len());
+
+        // the shared part branches on both sides
+        assert!(unitigs
+            .iter()
+            .any(|unitig| unitig == shared || unitig == &dna::revcomp(&shared[..])));
+        assert_eq!(unitigs.len(), 5);
+    }
+
+    #[test]
+    fn test_edge_orientation() {
+        let graph = DeBruijnGraph::from_sequences(&[b"AACGAT"], 4);
+        let aacg = Kmer::from_literal("AACG");
+        let edges = graph.out_edges(&aacg);
+        assert_eq!(edges.len(), 1);
+        // ACGA is entered on the strand of its canonical form or its reverse complement TCGT
+        let (node, strand) = &edges[0];
+        assert_eq!(node, &Kmer::from_literal("ACGA").canonical());
+        let expected = if *node == Kmer::from_literal("ACGA") {
+            Strand::Forward
+        } else {
+            Strand::Reverse
+        };
+        assert_eq!(*strand, expected);
+
+        // walking the reverse strand, the edge is traversed the other way
+        let in_edges = graph.in_edges(&Kmer::from_literal("CGTT"));
+        assert_eq!(in_edges.len(), 1);
+        assert_eq!(in_edges[0].0, *node);
+        assert!(graph.out_edges(&Kmer::from_literal("CGTT")).is_empty());
+    }
+
+    #[test]
+    fn test_edge_centric_graph() {
+        let kmer = |seq| Kmer::from_literal(seq);
+        let kmers = ["ACGT", "CGTA", "GTAC", "CGTT", "ACGT"];
+        let graph = EdgeCentricDeBruijnGraph::from_kmers(kmers.iter().map(|&seq| kmer(seq)));
+
+        let nodes: HashSet<Kmer> = graph.nodes().cloned().collect();
+        let expected: HashSet<Kmer> = ["ACG", "CGT", "GTA", "GTT", "TAC"]
+            .iter()
+            .map(|&seq| kmer(seq))
+            .collect();
+        assert_eq!(nodes, expected);
+
+        let edges: HashSet<(Kmer, Kmer)> = graph
+            .edges()
+            .map(|edge| (edge.prefix(), edge.suffix()))
+            .collect();
+        let expected: HashSet<(Kmer, Kmer)> = [
+            ("ACG", "CGT"),
+            ("CGT", "GTA"),
+            ("GTA", "TAC"),
+            ("CGT", "GTT"),
+        ]
+        .iter()
+        .map(|&(from, to)| (kmer(from), kmer(to)))
+        .collect();
+        assert_eq!(edges, expected);
+
+        assert_eq!(graph.successors(&kmer("ACG")), vec![kmer("CGT")]);
+        assert_eq!(
+            graph.successors(&kmer("CGT")),
+            vec![kmer("GTA"), kmer("GTT")]
+        );
+        assert!(graph.successors(&kmer("GTT")).is_empty());
+        assert!(graph.successors(&kmer("AAA")).is_empty());
+        assert!(graph.successors(&kmer("TAC")).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_edge_centric_graph_mixed_lengths() {
+        EdgeCentricDeBruijnGraph::from_kmers(vec![
+            Kmer::from_literal("ACG"),
+            Kmer::from_literal("ACGT"),
+        ]);
+    }
+
+    #[test]
+    fn test_debruijn_sequence() {
+        for k in 1..7 {
+            let seq = debruijn_sequence(k);
+            assert_eq!(seq.len(), 1 << (2 * k));
+            let cyclic = [&seq[..], &seq[..k - 1]].concat();
+            let kmers: HashSet<&[u8]> = cyclic.windows(k).collect();
+            assert_eq!(kmers.len(), seq.len());
+            for kmer in crate::data_structures::kmer::all_kmers(k) {
+                assert!(kmers.contains(kmer.decode().as_bytes()));
+            }
+        }
+        assert!(debruijn_sequence(0).is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+//! Error definitions for the `kmer` module.
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Snafu, Debug, PartialEq)]
+#[snafu(visibility = "pub")]
+pub enum Error {
+    #[snafu(display(
+        "invalid nucleotide '{}' at position {}",
+        char::from(*byte),
+        position
+    ))]
+    InvalidNucleotide { byte: u8, position: usize },
+    #[snafu(display(
+        "ambiguous nucleotide '{}' at position {}",
+        char::from(*byte),
+        position
+    ))]
+    AmbiguousNucleotide { byte: u8, position: usize },
+    #[snafu(display("invalid nucleotide '{}'", symbol))]
+    InvalidSymbol { symbol: char },
+    #[snafu(display("invalid 2-bit nucleotide code {}", code))]
+    InvalidCode { code: u8 },
+    #[snafu(display("expected {} encoded bytes, found {}", expected, found))]
+    LengthMismatch { expected: usize, found: usize },
+    #[snafu(display(
+        "k-mer length {} does not match the {} given nucleotides",
+        k,
+        found
+    ))]
+    SequenceLengthMismatch { k: usize, found: usize },
+    #[snafu(display("position {} is out of bounds for a k-mer of length {}", position, len))]
+    IndexOutOfBounds { position: usize, len: usize },
+    #[snafu(display("k-mer length {} exceeds the maximum of {}", k, max))]
+    KTooLarge { k: usize, max: usize },
+}
@@ -0,0 +1,3025 @@
+//Declares a structure with a variable length encoding scheme. Memory is allocated
+//for each 4 nucleotides as a single u8 in a vector of u8 values.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::iter::FromIterator;
+use std::ops::BitAnd;
+use std::ops::BitOr;
+use std::ops::BitXor;
+use std::ops::Not;
+
+use bio_types::strand::Strand;
+use fnv::FnvHasher;
+
+use crate::alphabets::dna;
+
+pub mod ambiguous;
+pub mod counter;
+pub mod debruijn;
+pub mod errors;
+pub mod sketch;
+pub use self::errors::{Error, Result};
+
+//Should I include mutable kmers and immutable kmers?
+
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "RawKmer")
+)]
+pub struct Kmer {
+    pub k: usize,
+    pub sequence: Vec<u8>,
+}
+
+/// The unchecked fields of a serialized `Kmer`, validated through `Kmer::from_raw` on
+/// deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawKmer {
+    k: usize,
+    sequence: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawKmer> for Kmer {
+    type Error = Error;
+
+    fn try_from(raw: RawKmer) -> Result<Self> {
+        Kmer::from_raw(raw.k, raw.sequence)
+    }
+}
+
+pub struct KmerIter {
+    pub kmer: Kmer,
+    pub position: usize,
+    pub nucleotide: u8,
+}
+
+/// A DNA nucleotide. The variants are ordered lexicographically, while their 2-bit codes (see
+/// `Nucleotide::to_u8`) are those of the packed `Kmer` representation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Nucleotide {
+    A,
+    C,
+    G,
+    T,
+}
+
+impl Nucleotide {
+    /// Convert a 2-bit code into its nucleotide.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCode` if `code` is larger than 3.
+    pub fn from_u8(code: u8) -> Result<Self> {
+        match code {
+            0 => Ok(Nucleotide::A),
+            1 => Ok(Nucleotide::G),
+            2 => Ok(Nucleotide::C),
+            3 => Ok(Nucleotide::T),
+            code => Err(Error::InvalidCode { code }),
+        }
+    }
+
+    /// The 2-bit code of the nucleotide, see `nuc_to_byte`.
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Nucleotide::A => 0,
+            Nucleotide::G => 1,
+            Nucleotide::C => 2,
+            Nucleotide::T => 3,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        byte_to_nuc(self.to_u8())
+    }
+
+    /// The complementary nucleotide, e.g. `T` for `A`.
+    pub fn complement(self) -> Self {
+        match self {
+            Nucleotide::A => Nucleotide::T,
+            Nucleotide::C => Nucleotide::G,
+            Nucleotide::G => Nucleotide::C,
+            Nucleotide::T => Nucleotide::A,
+        }
+    }
+}
+
+/// Iterator over the k-mers of a sequence, i.e. its windows of length `k`, from the first to the
+/// last. Each k-mer is obtained from the previous one with `Kmer::push_back` instead of being
+/// encoded from scratch. Sequences shorter than `k` yield no k-mers.
+///
+/// # Panics
+///
+/// The iterator panics if the sequence contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::{Kmer, Kmerizer};
+///
+/// let kmers: Vec<Kmer> = Kmerizer::new(3, b"ACGTT").collect();
+/// assert_eq!(
+///     kmers,
+///     vec![
+///         Kmer::from_literal("ACG"),
+///         Kmer::from_literal("CGT"),
+///         Kmer::from_literal("GTT")
+///     ]
+/// );
+/// ```
+pub struct Kmerizer<'a> {
+    pub k: usize,
+    pub position: usize,
+    pub sequence: &'a [u8],
+    pub current_kmer: Kmer,
+}
+
+impl<'a> Kmerizer<'a> {
+    /// Iterate over the k-mers of length `k` of `sequence`.
+    pub fn new(k: usize, sequence: &'a [u8]) -> Self {
+        Kmerizer {
+            k,
+            position: 0,
+            sequence,
+            current_kmer: Kmer::empty(k),
+        }
+    }
+}
+
+impl<'a> Iterator for Kmerizer<'a> {
+    type Item = Kmer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.k == 0 || self.position + self.k > self.sequence.len() {
+            return None;
+        }
+        if self.position == 0 {
+            self.current_kmer = Kmer::new(self.k, &self.sequence[..self.k]);
+        } else {
+            self.current_kmer
+                .push_back(self.sequence[self.position + self.k - 1]);
+        }
+        self.position += 1;
+        Some(self.current_kmer.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.k == 0 {
+            0
+        } else {
+            (self.sequence.len() + 1).saturating_sub(self.position + self.k)
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Kmerizer<'a> {}
+
+/// Random seeds of the ntHash rolling hash (Mohamadi et al., 2016), indexed by 2-bit code.
+const NT_HASH_SEEDS: [u64; 4] = [
+    0x3c8b_fbb3_95c6_0474, // A
+    0x2032_3ed0_8257_2324, // G
+    0x3193_c185_62a0_2b4c, // C
+    0x2955_49f5_4be2_4456, // T
+];
+
+fn nt_hash_seed(nucleotide: u8) -> u64 {
+    NT_HASH_SEEDS[nuc_to_byte(char::from(nucleotide)) as usize]
+}
+
+/// Compute the (forward strand) ntHash of `kmer`: the XOR of the seeds of its bases, each rotated
+/// left by its distance to the last base.
+///
+/// # Panics
+///
+/// Panics if `kmer` contains a character that [`Kmer::new`] rejects.
+pub fn nt_hash(kmer: &[u8]) -> u64 {
+    kmer.iter()
+        .fold(0, |hash, &nucleotide| hash.rotate_left(1) ^ nt_hash_seed(nucleotide))
+}
+
+/// Iterator over the k-mers of a sequence together with their ntHash, see `nt_hash`. The hash of
+/// each window is derived from that of the previous one in constant time, by rotating out the
+/// leaving base and rotating in the entering one.
+///
+/// # Panics
+///
+/// The iterator panics if the sequence contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::{nt_hash, Kmer, NtHashKmerizer};
+///
+/// let seq = b"ACGTTGCA";
+/// for (i, (kmer, hash)) in NtHashKmerizer::new(5, seq).enumerate() {
+///     assert_eq!(kmer, Kmer::new(5, &seq[i..i + 5]));
+///     assert_eq!(hash, nt_hash(&seq[i..i + 5]));
+/// }
+/// ```
+pub struct NtHashKmerizer<'a> {
+    kmers: Kmerizer<'a>,
+    hash: u64,
+}
+
+impl<'a> NtHashKmerizer<'a> {
+    /// Iterate over the k-mers of length `k` of `sequence` and their hashes.
+    pub fn new(k: usize, sequence: &'a [u8]) -> Self {
+        NtHashKmerizer {
+            kmers: Kmerizer::new(k, sequence),
+            hash: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for NtHashKmerizer<'a> {
+    type Item = (Kmer, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.kmers.position;
+        let kmer = self.kmers.next()?;
+        let (k, seq) = (self.kmers.k, self.kmers.sequence);
+        self.hash = if pos == 0 {
+            nt_hash(&seq[..k])
+        } else {
+            self.hash.rotate_left(1)
+                ^ nt_hash_seed(seq[pos - 1]).rotate_left(k as u32)
+                ^ nt_hash_seed(seq[pos + k - 1])
+        };
+        Some((kmer, self.hash))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.kmers.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for NtHashKmerizer<'a> {}
+
+/// Iterate over the k-mers of `seq` from the first window to the last, together with their
+/// start positions.
+///
+/// # Panics
+///
+/// The iterator panics if `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::{kmers_with_pos, Kmer};
+///
+/// let kmers: Vec<(usize, Kmer)> = kmers_with_pos(b"ACGT", 3).collect();
+/// assert_eq!(
+///     kmers,
+///     vec![(0, Kmer::from_literal("ACG")), (1, Kmer::from_literal("CGT"))]
+/// );
+/// ```
+pub fn kmers_with_pos(seq: &[u8], k: usize) -> impl Iterator<Item = (usize, Kmer)> + '_ {
+    kmers_rev(seq, k).rev().enumerate()
+}
+
+/// Collect the k-mers of the concatenation of `seqs`, including the k-mers spanning the junction
+/// between consecutive sequences. If `wrap` is true, the concatenation is treated as circular and
+/// the k-mers crossing from its end back to its start are emitted as well, such that a circular
+/// sequence of length `n` yields `n` k-mers.
+///
+/// # Panics
+///
+/// Panics if a sequence contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::kmerize_joined;
+///
+/// let kmers: Vec<String> = kmerize_joined(&[b"ACG", b"TT"], 3, true)
+///     .iter()
+///     .map(|kmer| kmer.decode())
+///     .collect();
+/// assert_eq!(kmers, vec!["ACG", "CGT", "GTT", "TTA", "TAC"]);
+/// ```
+pub fn kmerize_joined(seqs: &[&[u8]], k: usize, wrap: bool) -> Vec<Kmer> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut joined = seqs.concat();
+    if wrap {
+        let len = joined.len();
+        joined = joined.iter().cycle().take(len + k - 1).cloned().collect();
+    }
+    joined.windows(k).map(|window| Kmer::new(k, window)).collect()
+}
+
+/// Iterator adapter suppressing k-mers identical to the immediately preceding one, see
+/// `dedup_consecutive`.
+pub struct DedupConsecutive<I: Iterator<Item = Kmer>> {
+    kmers: I,
+    previous: Option<Kmer>,
+}
+
+/// Suppress k-mers that are identical to the immediately preceding one, e.g. the repeated
+/// windows of a homopolymer run. K-mers that reoccur later are still yielded.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::{dedup_consecutive, Kmer, Kmerizer};
+///
+/// let kmers: Vec<Kmer> = dedup_consecutive(Kmerizer::new(2, b"AAAAA")).collect();
+/// assert_eq!(kmers, vec![Kmer::from_literal("AA")]);
+/// ```
+pub fn dedup_consecutive<I: IntoIterator<Item = Kmer>>(kmers: I) -> DedupConsecutive<I::IntoIter> {
+    DedupConsecutive {
+        kmers: kmers.into_iter(),
+        previous: None,
+    }
+}
+
+impl<I: Iterator<Item = Kmer>> Iterator for DedupConsecutive<I> {
+    type Item = Kmer;
+
+    fn next(&mut self) -> Option<Kmer> {
+        for kmer in &mut self.kmers {
+            if self.previous.as_ref() != Some(&kmer) {
+                self.previous = Some(kmer.clone());
+                return Some(kmer);
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the k-mers of a sequence from its end to its start, see `kmers_rev`.
+pub struct KmersRev<'a> {
+    k: usize,
+    sequence: &'a [u8],
+    // start positions of the windows not yielded yet
+    start: usize,
+    end: usize,
+    // the last k-mers yielded from either end, rolled to obtain the next ones
+    front: Option<Kmer>,
+    back: Option<Kmer>,
+}
+
+/// Iterate over the k-mers of `seq` from the last window to the first. Each k-mer is obtained
+/// from the previous one with `Kmer::push_front` instead of being encoded from scratch. Iterating
+/// from the other end (with `next_back`) yields the windows from the first to the last,
+/// maintained with `Kmer::push_back`.
+///
+/// # Panics
+///
+/// The iterator panics if `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::{kmers_rev, Kmer};
+///
+/// let kmers: Vec<Kmer> = kmers_rev(b"ACGTT", 3).collect();
+/// assert_eq!(
+///     kmers,
+///     vec![
+///         Kmer::from_literal("GTT"),
+///         Kmer::from_literal("CGT"),
+///         Kmer::from_literal("ACG")
+///     ]
+/// );
+/// ```
+pub fn kmers_rev(seq: &[u8], k: usize) -> KmersRev<'_> {
+    let windows = if k == 0 || seq.len() < k {
+        0
+    } else {
+        seq.len() - k + 1
+    };
+    KmersRev {
+        k,
+        sequence: seq,
+        start: 0,
+        end: windows,
+        front: None,
+        back: None,
+    }
+}
+
+impl<'a> Iterator for KmersRev<'a> {
+    type Item = Kmer;
+
+    fn next(&mut self) -> Option<Kmer> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        let pos = self.end;
+        let kmer = match self.front.take() {
+            Some(mut kmer) => {
+                kmer.push_front(self.sequence[pos]);
+                kmer
+            }
+            None => Kmer::new(self.k, &self.sequence[pos..pos + self.k]),
+        };
+        self.front = Some(kmer.clone());
+        Some(kmer)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for KmersRev<'a> {
+    fn next_back(&mut self) -> Option<Kmer> {
+        if self.start == self.end {
+            return None;
+        }
+        let pos = self.start;
+        self.start += 1;
+        let kmer = match self.back.take() {
+            Some(mut kmer) => {
+                kmer.push_back(self.sequence[pos + self.k - 1]);
+                kmer
+            }
+            None => Kmer::new(self.k, &self.sequence[pos..pos + self.k]),
+        };
+        self.back = Some(kmer.clone());
+        Some(kmer)
+    }
+}
+
+impl<'a> ExactSizeIterator for KmersRev<'a> {}
+
+impl Kmer {
+    /// Create a k-mer of length `len` from the nucleotides in `byte_seq`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte_seq` does not have length `len` or contains a character that
+    /// [`Kmer::try_new`] rejects. See `Kmer::try_new` for a fallible version.
+    pub fn new(len: usize, byte_seq: &[u8]) -> Self {
+        Kmer::try_new(len, byte_seq).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a k-mer of length `len` from the nucleotides in `byte_seq`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SequenceLengthMismatch` if `byte_seq` does not have length `len`, and
+    /// `Error::InvalidNucleotide` for the first character that is not a nucleotide, e.g. an `N`.
+    /// The nucleotides are `A`, `C`, `G` and `T` in either case, as well as `U` (or `u`), which
+    /// is read as `T`; see [`Kmer::encode`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::{Error, Kmer};
+    ///
+    /// assert!(Kmer::try_new(4, b"GaTT").is_ok());
+    /// assert_eq!(
+    ///     Kmer::try_new(3, b"GATT"),
+    ///     Err(Error::SequenceLengthMismatch { k: 3, found: 4 })
+    /// );
+    /// assert_eq!(
+    ///     Kmer::try_new(4, b"GNTT"),
+    ///     Err(Error::InvalidNucleotide {
+    ///         byte: b'N',
+    ///         position: 1
+    ///     })
+    /// );
+    /// ```
+    pub fn try_new(len: usize, byte_seq: &[u8]) -> Result<Self> {
+        if len != byte_seq.len() {
+            return Err(Error::SequenceLengthMismatch {
+                k: len,
+                found: byte_seq.len(),
+            });
+        }
+        let mut kmer = Kmer::with_capacity(len);
+        kmer.try_encode(byte_seq)?;
+        Ok(kmer)
+    }
+
+    /// Create an empty k-mer of length `k` whose buffer can hold `k` encoded bases without
+    /// reallocating.
+    pub fn with_capacity(k: usize) -> Self {
+        Kmer {
+            k,
+            sequence: Vec::with_capacity(k.div_ceil(4)),
+        }
+    }
+
+    pub fn empty(len: usize) -> Self {
+        Kmer{
+            k: len,
+            sequence: Vec::new(),
+        }
+    }
+
+    /// Create a k-mer from a string of nucleotides.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `str_literal` contains a character that [`Kmer::new`] rejects. See
+    /// `Kmer::try_from_literal` for a fallible version.
+    pub fn from_literal(str_literal: &str) -> Self {
+        Kmer::new(str_literal.len(), str_literal.as_bytes())
+    }
+
+    /// Create a k-mer from a string of nucleotides.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidNucleotide` for the first character that [`Kmer::try_new`]
+    /// rejects.
+    pub fn try_from_literal(str_literal: &str) -> Result<Self> {
+        Kmer::try_new(str_literal.len(), str_literal.as_bytes())
+    }
+
+    /// Parse a k-mer from a string of uppercase `A`, `C`, `G` and `T`, returning `None` for any
+    /// other character (including lowercase bases and `N`). This is a fast path for clean input
+    /// that neither panics like `Kmer::from_literal` nor builds an error like
+    /// `Kmer::try_encode`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// assert_eq!(Kmer::from_acgt("GATTACA"), Some(Kmer::from_literal("GATTACA")));
+    /// assert_eq!(Kmer::from_acgt("GATTNCA"), None);
+    /// ```
+    pub fn from_acgt(s: &str) -> Option<Kmer> {
+        let bases = s.as_bytes();
+        let mut sequence = Vec::with_capacity(bases.len().div_ceil(4));
+        for chunk in bases.chunks(4) {
+            let mut byte = 0;
+            for (i, &base) in chunk.iter().enumerate() {
+                let code = ACGT_CODES[base as usize];
+                if code == INVALID_CODE {
+                    return None;
+                }
+                byte |= code << (2 * i);
+            }
+            sequence.push(byte);
+        }
+        Some(Kmer {
+            k: bases.len(),
+            sequence,
+        })
+    }
+
+    /// The number of bases of the k-mer, i.e. `k`.
+    pub fn len(&self) -> usize {
+        self.k
+    }
+
+    /// Whether the k-mer has no bases.
+    pub fn is_empty(&self) -> bool {
+        self.k == 0
+    }
+
+    /// Build a k-mer from the bases of `seq` at the given `positions` (in the given order), e.g.
+    /// for spaced seeds. The resulting k-mer has length `positions.len()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a position is out of bounds for `seq` or points to a character that
+    /// [`Kmer::new`] rejects.
+    pub fn project(seq: &[u8], positions: &[usize]) -> Self {
+        let bases: Vec<u8> = positions.iter().map(|&pos| seq[pos]).collect();
+        Kmer::new(bases.len(), &bases)
+    }
+
+    /// Replace the contents of the k-mer with the 2-bit encoding of `byte_seq`. RNA is accepted
+    /// as well: `U` is encoded like `T`. Lowercase (e.g. soft-masked) nucleotides are encoded
+    /// like uppercase ones, so decoding always yields uppercase.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte_seq` contains any other character, e.g. an `N`. See `Kmer::try_encode`
+    /// for a fallible version.
+    pub fn encode(&mut self, byte_seq: &[u8]) {
+        if let Err(e) = self.try_encode(byte_seq) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Replace the contents of the k-mer with the 2-bit encoding of `byte_seq`, reading `U` as
+    /// `T` and lowercase as uppercase. `k` is set to the length of `byte_seq`. The existing
+    /// buffer is reused, so encoding into a k-mer from `Kmer::with_capacity` does not allocate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidNucleotide` for the first character that [`Kmer::encode`] does not
+    /// accept. The k-mer is left unchanged in that case.
+    pub fn try_encode(&mut self, byte_seq: &[u8]) -> Result<()> {
+        if let Some(position) = byte_seq
+            .iter()
+            .position(|&b| NUCLEOTIDE_CODES[b as usize] == INVALID_CODE)
+        {
+            return Err(Error::InvalidNucleotide {
+                byte: byte_seq[position],
+                position,
+            });
+        }
+        self.k = byte_seq.len();
+        self.sequence.clear();
+        self.sequence.reserve(byte_seq.len().div_ceil(4));
+        for chunk in byte_seq.chunks(4) {
+            let mut bit_seq: u8 = 0;
+            for (i, &nucleotide) in chunk.iter().enumerate() {
+                bit_seq |= NUCLEOTIDE_CODES[nucleotide as usize] << (2 * i);
+            }
+            self.sequence.push(bit_seq)
+        }
+        Ok(())
+    }
+
+    /// Decode the k-mer into its nucleotide sequence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of encoded bytes does not fit `k`. See `Kmer::try_decode` for a
+    /// fallible version.
+    pub fn decode(&self) -> String {
+        self.try_decode().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Decode the k-mer into its nucleotide sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::LengthMismatch` if the number of encoded bytes does not fit `k`.
+    pub fn try_decode(&self) -> Result<String> {
+        let expected = self.k.div_ceil(4);
+        if self.sequence.len() != expected {
+            return Err(Error::LengthMismatch {
+                expected,
+                found: self.sequence.len(),
+            });
+        }
+        Ok(decode_bytes(&self.sequence, self.k))
+    }
+
+    /// Return the bases of the k-mer as a string, e.g. for writing them to a FASTA file. This is
+    /// the same as `Kmer::decode`, whereas `Display` adds the length for debugging.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let kmer = Kmer::from_literal("GATTACA");
+    /// assert_eq!(kmer.sequence_string(), "GATTACA");
+    /// assert_eq!(kmer.to_string(), "Vmer[7]: GATTACA");
+    /// ```
+    pub fn sequence_string(&self) -> String {
+        self.decode()
+    }
+
+    /// Decode the k-mer into its RNA sequence, i.e. with `U` instead of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let kmer = Kmer::from_literal("GATTACA");
+    /// assert_eq!(kmer.transcribe(), "GAUUACA");
+    /// assert_eq!(Kmer::from_literal("GAUUACA"), kmer);
+    /// ```
+    pub fn transcribe(&self) -> String {
+        self.decode().replace('T', "U")
+    }
+
+    /// Decode the k-mer into its nucleotide sequence, inserting a newline after each
+    /// `line_width` bases (but not at the end), as used in FASTA files.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line_width` is zero.
+    pub fn decode_wrapped(&self, line_width: usize) -> String {
+        assert!(line_width > 0, "line width must be positive");
+        let decoded = self.decode();
+        let mut wrapped = String::with_capacity(decoded.len() + decoded.len() / line_width);
+        for (i, base) in decoded.chars().enumerate() {
+            if i > 0 && i % line_width == 0 {
+                wrapped.push('\n');
+            }
+            wrapped.push(base);
+        }
+        wrapped
+    }
+
+    /// Write the decoded nucleotide sequence to `writer` in lines of `line_width` bases, each
+    /// terminated by a newline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line_width` is zero.
+    pub fn write_wrapped<W: io::Write>(&self, writer: &mut W, line_width: usize) -> io::Result<()> {
+        assert!(line_width > 0, "line width must be positive");
+        for line in self.decode().as_bytes().chunks(line_width) {
+            writer.write_all(line)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    //Does not consume the Kmer and returns a new Kmer
+    pub fn make_complement(&self) -> Kmer {
+        let complement = Kmer::new(self.k, self.decode().as_bytes());
+        !complement
+    }
+
+    //Modifies the existing Kmer
+    pub fn complement(&mut self) {
+        self.sequence = self.sequence.iter().map(|x| !x).collect();
+        self.mask_padding();
+    }
+
+    /// Return the k-mer with its bases in reverse order, without complementing them, e.g. `TGCA`
+    /// for `ACGT`. The 2-bit codes are rearranged without decoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let kmer = Kmer::from_literal("GATTACA");
+    /// assert_eq!(kmer.make_reverse(), Kmer::from_literal("ACATTAG"));
+    /// assert_eq!(kmer.make_reverse_complement(), Kmer::from_literal("TGTAATC"));
+    /// ```
+    pub fn make_reverse(&self) -> Kmer {
+        let mut sequence = vec![0; self.k.div_ceil(4)];
+        for i in 0..self.k {
+            sequence[i / 4] |= self.index(self.k - 1 - i) << (2 * (i % 4));
+        }
+        Kmer { k: self.k, sequence }
+    }
+
+    /// Reverse the order of the bases in place, without complementing them.
+    pub fn reverse(&mut self) {
+        *self = self.make_reverse();
+    }
+
+    pub fn make_reverse_complement(&self) -> Kmer {
+        let reverse: String = self.decode().chars().rev().collect();
+        let kmer = Kmer::from_literal(reverse.as_str());
+        !kmer
+    }
+
+    pub fn reverse_complement(&mut self) {
+        let reverse: String = self.decode().chars().rev().collect();
+        self.encode(reverse.as_bytes());
+        self.complement();
+    }
+
+    /// Describe the k-mer together with its reverse complement for debugging strand-aware code,
+    /// e.g. `GATTACA (rc: TGTAATC)`.
+    pub fn describe(&self) -> String {
+        let forward = self.decode();
+        let revcomp = String::from_utf8(dna::revcomp(forward.as_bytes())).unwrap();
+        format!("{} (rc: {})", forward, revcomp)
+    }
+
+    /// Return the canonical form of the k-mer, i.e. the lexicographically smaller of the k-mer
+    /// and its reverse complement. A k-mer and its reverse complement share the same canonical
+    /// form.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let canonical = Kmer::from_literal("GGTAC");
+    /// assert_eq!(Kmer::from_literal("GTACC").canonical(), canonical);
+    /// assert!(canonical.is_canonical());
+    /// ```
+    pub fn canonical(&self) -> Kmer {
+        let revcomp = self.make_reverse_complement();
+        if revcomp < *self {
+            revcomp
+        } else {
+            let mut forward = self.clone();
+            forward.mask_padding();
+            forward
+        }
+    }
+
+    /// Whether the k-mer is its own canonical form, i.e. not larger than its reverse complement.
+    pub fn is_canonical(&self) -> bool {
+        *self <= self.make_reverse_complement()
+    }
+
+    /// Replace the k-mer by its canonical form in place, and return which strand that was:
+    /// `Strand::Forward` if the k-mer already was canonical, `Strand::Reverse` if it was replaced
+    /// by its reverse complement, and `Strand::Unknown` if it is its own reverse complement.
+    pub fn make_canonical(&mut self) -> Strand {
+        let revcomp = Kmer::new(self.k, &dna::revcomp(self.decode().as_bytes()));
+        self.mask_padding();
+        match revcomp.cmp(self) {
+            Ordering::Less => {
+                *self = revcomp;
+                Strand::Reverse
+            }
+            Ordering::Equal => Strand::Unknown,
+            Ordering::Greater => Strand::Forward,
+        }
+    }
+
+    /// Pack the k-mer into a single `u64`, with the first base in the lowest two bits. Returns
+    /// `None` if `k > 32`, i.e. if the k-mer does not fit. The packed value is a cheap key for
+    /// hash tables, see `counter::U64KmerCounter`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let kmer = Kmer::from_literal("GATTACA");
+    /// let packed = kmer.to_u64().unwrap();
+    /// assert_eq!(Kmer::from_u64(7, packed), kmer);
+    /// assert_eq!(Kmer::from_literal(&"A".repeat(33)).to_u64(), None);
+    /// ```
+    pub fn to_u64(&self) -> Option<u64> {
+        if self.k > 32 {
+            return None;
+        }
+        let bytes: Vec<u8> = meaningful_bytes(self).collect();
+        Some(
+            bytes
+                .iter()
+                .rev()
+                .fold(0, |packed, &byte| (packed << 8) | u64::from(byte)),
+        )
+    }
+
+    /// Unpack a k-mer of length `k` from the lowest `2 * k` bits of `bits`, as packed by
+    /// `Kmer::to_u64`. Higher bits are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > 32`.
+    pub fn from_u64(k: usize, bits: u64) -> Kmer {
+        assert!(k <= 32, "a k-mer packed into a u64 has at most 32 bases");
+        let mut kmer = Kmer {
+            k,
+            sequence: bits.to_le_bytes()[..k.div_ceil(4)].to_vec(),
+        };
+        kmer.mask_padding();
+        kmer
+    }
+
+    /// Build a k-mer of length `k` from its packed 2-bit representation, e.g. as obtained from
+    /// another program or a previous `Kmer::sequence`. Unused bits of the last byte are cleared,
+    /// such that the k-mer compares equal to one encoded from the same bases.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::LengthMismatch` if `sequence` does not consist of exactly `(k + 3) / 4`
+    /// bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let kmer = Kmer::from_literal("ACGTA");
+    /// assert_eq!(Kmer::from_raw(5, kmer.sequence.clone()), Ok(kmer));
+    /// assert!(Kmer::from_raw(5, vec![0]).is_err());
+    /// ```
+    pub fn from_raw(k: usize, sequence: Vec<u8>) -> Result<Kmer> {
+        if sequence.len() != k.div_ceil(4) {
+            return Err(Error::LengthMismatch {
+                expected: k.div_ceil(4),
+                found: sequence.len(),
+            });
+        }
+        let mut kmer = Kmer { k, sequence };
+        kmer.mask_padding();
+        Ok(kmer)
+    }
+
+    /// Slide the k-mer one base forward: drop the first base and append `nucleotide` at the end,
+    /// keeping the length `k`. This is done in place on the packed representation, without
+    /// reallocating, and is the building block of `Kmerizer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nucleotide` is not a nucleotide accepted by [`Kmer::new`].
+    pub fn push_back(&mut self, nucleotide: u8) {
+        let code = nuc_to_byte(char::from(nucleotide));
+        if self.k == 0 {
+            return;
+        }
+        self.mask_padding();
+        let last = self.sequence.len() - 1;
+        for i in 0..last {
+            self.sequence[i] = (self.sequence[i] >> 2) | (self.sequence[i + 1] << 6);
+        }
+        self.sequence[last] >>= 2;
+        let pos = self.k - 1;
+        self.sequence[pos / 4] |= code << (2 * (pos % 4));
+    }
+
+    /// Slide the k-mer one base backward: drop the last base and prepend `nucleotide` at the
+    /// front, keeping the length `k`. This is done in place on the packed representation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nucleotide` is not a nucleotide accepted by [`Kmer::new`].
+    pub fn push_front(&mut self, nucleotide: u8) {
+        let code = nuc_to_byte(char::from(nucleotide));
+        if self.k == 0 {
+            return;
+        }
+        for i in (1..self.sequence.len()).rev() {
+            self.sequence[i] = (self.sequence[i] << 2) | (self.sequence[i - 1] >> 6);
+        }
+        self.sequence[0] = (self.sequence[0] << 2) | code;
+        self.mask_padding();
+    }
+
+    /// Append `nucleotide` at the end, growing the k-mer by one base.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nucleotide` is not a nucleotide accepted by [`Kmer::new`]. See
+    /// `Kmer::try_push` for a fallible version.
+    pub fn push(&mut self, nucleotide: u8) {
+        if let Err(e) = self.try_push(nucleotide) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Append `nucleotide` at the end, growing the k-mer by one base.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidNucleotide` if `nucleotide` is not a nucleotide accepted by
+    /// [`Kmer::try_new`]. The k-mer is left unchanged in that case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let mut kmer = Kmer::from_literal("GATTAC");
+    /// kmer.try_push(b'A').unwrap();
+    /// assert_eq!(kmer, Kmer::from_literal("GATTACA"));
+    /// assert!(kmer.try_push(b'N').is_err());
+    /// ```
+    pub fn try_push(&mut self, nucleotide: u8) -> Result<()> {
+        let code = NUCLEOTIDE_CODES[nucleotide as usize];
+        if code == INVALID_CODE {
+            return Err(Error::InvalidNucleotide {
+                byte: nucleotide,
+                position: self.k,
+            });
+        }
+        if self.k.is_multiple_of(4) {
+            self.sequence.push(code);
+        } else {
+            self.mask_padding();
+            let last = self.sequence.len() - 1;
+            self.sequence[last] |= code << (2 * (self.k % 4));
+        }
+        self.k += 1;
+        Ok(())
+    }
+
+    /// The four k-mers following this one in a de Bruijn graph, i.e. the k-mers obtained by
+    /// dropping the first base and appending `A`, `C`, `G` or `T` (in that order).
+    pub fn successors(&self) -> [Kmer; 4] {
+        let successor = |base| {
+            let mut kmer = self.clone();
+            kmer.push_back(base);
+            kmer
+        };
+        [
+            successor(b'A'),
+            successor(b'C'),
+            successor(b'G'),
+            successor(b'T'),
+        ]
+    }
+
+    /// The four k-mers preceding this one in a de Bruijn graph, i.e. the k-mers obtained by
+    /// dropping the last base and prepending `A`, `C`, `G` or `T` (in that order).
+    pub fn predecessors(&self) -> [Kmer; 4] {
+        let predecessor = |base| {
+            let mut kmer = self.clone();
+            kmer.push_front(base);
+            kmer
+        };
+        [
+            predecessor(b'A'),
+            predecessor(b'C'),
+            predecessor(b'G'),
+            predecessor(b'T'),
+        ]
+    }
+
+    /// Clear the unused bits of the last byte, such that equal k-mers have equal bytes.
+    fn mask_padding(&mut self) {
+        let used = self.k % 4;
+        if used != 0 {
+            if let Some(last) = self.sequence.last_mut() {
+                *last &= (1 << (2 * used)) - 1;
+            }
+        }
+    }
+
+    /// Return the k-mer of the bases `start..end`, extracted from the packed bytes without
+    /// decoding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end` or `end > k`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let kmer = Kmer::from_literal("ACGTACGT");
+    /// assert_eq!(kmer.subsequence(2, 6).decode(), "GTAC");
+    /// ```
+    pub fn subsequence(&self, start: usize, end: usize) -> Kmer {
+        assert!(
+            start <= end && end <= self.k,
+            "range {}..{} is out of bounds for a k-mer of length {}",
+            start,
+            end,
+            self.k
+        );
+        let k = end - start;
+        let (first, shift) = (start / 4, 2 * (start % 4));
+        let sequence = (0..k.div_ceil(4))
+            .map(|i| {
+                let low = self.sequence[first + i] >> shift;
+                match self.sequence.get(first + i + 1) {
+                    Some(&high) if shift > 0 => low | (high << (8 - shift)),
+                    _ => low,
+                }
+            })
+            .collect();
+        let mut kmer = Kmer { k, sequence };
+        kmer.mask_padding();
+        kmer
+    }
+
+    /// Return the (k-1)-mer of all bases but the last.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the k-mer is empty.
+    pub fn prefix(&self) -> Kmer {
+        assert!(self.k > 0, "the empty k-mer has no prefix");
+        self.subsequence(0, self.k - 1)
+    }
+
+    /// Return the (k-1)-mer of all bases but the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the k-mer is empty.
+    pub fn suffix(&self) -> Kmer {
+        assert!(self.k > 0, "the empty k-mer has no suffix");
+        self.subsequence(1, self.k)
+    }
+
+    /// Whether `other` can follow this k-mer in a de Bruijn graph, i.e. the suffix of this
+    /// k-mer is the prefix of `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let kmer = Kmer::from_literal("ACGT");
+    /// assert!(kmer.overlaps(&Kmer::from_literal("CGTA")));
+    /// assert!(!Kmer::from_literal("CGTA").overlaps(&kmer));
+    /// ```
+    pub fn overlaps(&self, other: &Kmer) -> bool {
+        self.suffix() == other.prefix()
+    }
+
+    /// Append the bases of `other`, growing the k-mer to length `self.k + other.k`. If `k` is not
+    /// a multiple of 4, the bases of `other` are shifted into the unused bits of the last byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let mut kmer = Kmer::from_literal("ACG");
+    /// kmer.append(&Kmer::from_literal("TTA"));
+    /// assert_eq!(kmer, Kmer::from_literal("ACGTTA"));
+    /// ```
+    pub fn append(&mut self, other: &Kmer) {
+        self.mask_padding();
+        let shift = 2 * (self.k % 4);
+        for byte in meaningful_bytes(other) {
+            match self.sequence.last_mut() {
+                Some(last) if shift > 0 => {
+                    *last |= byte << shift;
+                    self.sequence.push(byte >> (8 - shift));
+                }
+                _ => self.sequence.push(byte),
+            }
+        }
+        self.k += other.k;
+        self.sequence.truncate(self.k.div_ceil(4));
+    }
+
+    /// Return the concatenation of the k-mer and `other`, see `Kmer::append`.
+    pub fn concat(&self, other: &Kmer) -> Kmer {
+        let mut kmer = self.clone();
+        kmer.append(other);
+        kmer
+    }
+
+    /// Cyclically shift the bases `n` positions to the left, e.g. rotating `ACGTA` left by 2
+    /// yields `GTAAC`. Rotating by a multiple of `k` leaves the k-mer unchanged.
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.k == 0 {
+            return;
+        }
+        let n = n % self.k;
+        if n == 0 {
+            return;
+        }
+        let mut rotated = vec![0u8; self.sequence.len()];
+        for i in 0..self.k {
+            rotated[i / 4] |= self.index((i + n) % self.k) << (2 * (i % 4));
+        }
+        self.sequence = rotated;
+    }
+
+    /// Cyclically shift the bases `n` positions to the right, e.g. rotating `ACGTA` right by 2
+    /// yields `TAACG`. Rotating by a multiple of `k` leaves the k-mer unchanged.
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.k == 0 {
+            return;
+        }
+        self.rotate_left(self.k - n % self.k);
+    }
+
+    /// Whether `other` is a cyclic rotation of the k-mer, e.g. `GTAC` is a rotation of `ACGT`.
+    /// K-mers of different lengths are never rotations of each other.
+    pub fn is_rotation_of(&self, other: &Kmer) -> bool {
+        if self.k != other.k {
+            return false;
+        }
+        let decoded = self.decode();
+        [decoded.as_str(), decoded.as_str()]
+            .concat()
+            .contains(&other.decode())
+    }
+
+    /// Iterate over the bases of the k-mer as `Nucleotide`s. Unlike `IntoIterator`, which yields
+    /// the 2-bit codes of the bases, this does not consume the k-mer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::{Kmer, Nucleotide};
+    ///
+    /// let bases: Vec<Nucleotide> = Kmer::from_literal("GAT").iter_nucleotides().collect();
+    /// assert_eq!(bases, vec![Nucleotide::G, Nucleotide::A, Nucleotide::T]);
+    /// ```
+    pub fn iter_nucleotides(&self) -> impl Iterator<Item = Nucleotide> + '_ {
+        (0..self.k).map(move |position| Nucleotide::from_u8(self.index(position)).unwrap())
+    }
+
+    /// Return the 2-bit code of the base at `position`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is not smaller than `k`. See `Kmer::try_index` for a fallible
+    /// version.
+    pub fn index(&self, position: usize) -> u8 {
+        self.try_index(position).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Return the 2-bit code of the base at `position`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::IndexOutOfBounds` if `position` is not smaller than `k` and
+    /// `Error::LengthMismatch` if the encoded bytes do not cover `position`.
+    pub fn try_index(&self, position: usize) -> Result<u8> {
+        if position >= self.k {
+            return Err(Error::IndexOutOfBounds {
+                position,
+                len: self.k,
+            });
+        }
+        let byte = self.sequence.get(position / 4).ok_or(Error::LengthMismatch {
+            expected: self.k.div_ceil(4),
+            found: self.sequence.len(),
+        })?;
+        let bit_mask: u8 = 0b00000011;
+        let shift = 2 * (position % 4);
+        Ok((byte & (bit_mask << shift)) >> (shift))
+    }
+
+    /// Hash the k-mer and keep the lowest `bits` bits, e.g. to address a table of `2^bits`
+    /// entries such as a `sketch::FoldedKmerSet`. The hash does not depend on the platform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is larger than 64.
+    pub fn fold_to(&self, bits: u32) -> u64 {
+        assert!(bits <= 64, "cannot fold a k-mer to more than 64 bits");
+        let hash = mix64(hash_kmer(self));
+        if bits == 64 {
+            hash
+        } else {
+            hash & ((1 << bits) - 1)
+        }
+    }
+
+    /// The position of the smallest s-mer (substring of length `s`) within the k-mer, ordering
+    /// s-mers by their hash `Kmer::fold_to(64)` and breaking ties in favour of the leftmost one.
+    /// This is the primitive of syncmers: a k-mer is an open syncmer if the position is 0 and a
+    /// closed syncmer if it is 0 or `k - s`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is zero or larger than `k`.
+    pub fn smallest_smer_position(&self, s: usize) -> usize {
+        assert!(s > 0 && s <= self.k, "s must be between 1 and k");
+        let bases = self.decode().into_bytes();
+        let mut smer = Kmer::new(s, &bases[..s]);
+        let mut best = (smer.fold_to(64), 0);
+        for (pos, &base) in bases.iter().enumerate().skip(s) {
+            smer.push_back(base);
+            let hash = smer.fold_to(64);
+            if hash < best.0 {
+                best = (hash, pos + 1 - s);
+            }
+        }
+        best.1
+    }
+
+    /// Count the positions at which the k-mer and `other` have different bases. Mismatches are
+    /// counted on the XOR of the packed bytes, where each differing base leaves a non-zero 2-bit
+    /// group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the k-mers have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let a = Kmer::from_literal("AAAA");
+    /// assert_eq!(a.hamming_distance(&Kmer::from_literal("AACA")), 1);
+    /// ```
+    pub fn hamming_distance(&self, other: &Kmer) -> usize {
+        assert_eq!(self.k, other.k, "k-mers must have the same length");
+        meaningful_bytes(self)
+            .zip(meaningful_bytes(other))
+            .map(|(a, b)| {
+                let xor = a ^ b;
+                ((xor | (xor >> 1)) & 0b0101_0101).count_ones() as usize
+            })
+            .sum()
+    }
+
+    /// Return the `3 * k` k-mers that differ from this one at exactly one position, e.g. for
+    /// error correction against a `counter::KmerCounter`. They are ordered by position, and by
+    /// alternative base (`A < C < G < T`) within the same position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// let neighbors = Kmer::from_literal("GATTACA").neighbors_1mismatch();
+    /// assert_eq!(neighbors.len(), 21);
+    /// assert_eq!(neighbors[0], Kmer::from_literal("AATTACA"));
+    /// ```
+    pub fn neighbors_1mismatch(&self) -> Vec<Kmer> {
+        let mut kmer = self.clone();
+        kmer.mask_padding();
+        let mut neighbors = Vec::with_capacity(3 * self.k);
+        for position in 0..self.k {
+            let shift = 2 * (position % 4);
+            let current = self.index(position);
+            for &base in b"ACGT" {
+                let code = ACGT_CODES[base as usize];
+                if code == current {
+                    continue;
+                }
+                let mut neighbor = kmer.clone();
+                let byte = &mut neighbor.sequence[position / 4];
+                *byte = (*byte & !(3 << shift)) | (code << shift);
+                neighbors.push(neighbor);
+            }
+        }
+        neighbors
+    }
+
+    /// Return the fraction of bases that are `G` or `C`, computed on the packed bytes: these are
+    /// the 2-bit codes whose two bits differ. An empty k-mer has a GC content of 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// assert_eq!(Kmer::from_literal("GATTACA").gc_content(), 2.0 / 7.0);
+    /// ```
+    pub fn gc_content(&self) -> f64 {
+        if self.k == 0 {
+            return 0.0;
+        }
+        let gc: u32 = meaningful_bytes(self)
+            .map(|byte| ((byte ^ (byte >> 1)) & 0b0101_0101).count_ones())
+            .sum();
+        f64::from(gc) / self.k as f64
+    }
+
+    /// Count the bases of the k-mer in a single pass over the packed bytes. The counts are
+    /// indexed by 2-bit code, i.e. they are the numbers of `A`, `G`, `C` and `T`, in that order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// assert_eq!(Kmer::from_literal("GATTACA").base_counts(), [3, 1, 1, 2]);
+    /// ```
+    pub fn base_counts(&self) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for (i, &byte) in self.sequence.iter().enumerate() {
+            let bases = self.k.saturating_sub(4 * i).min(4);
+            for j in 0..bases {
+                counts[((byte >> (2 * j)) & 3) as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// The index of the k-mer in the lexicographic enumeration of all 4^k k-mers of length `k`
+    /// (with `A < C < G < T`, see `all_kmers`). Unlike `Kmer::to_u64`, which uses the packed
+    /// representation, the rank orders k-mers alphabetically. It is a `u128` rather than a `u64`
+    /// so that it covers k-mers of up to 64 bases instead of the 32 that fit into `to_u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > 64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::data_structures::kmer::Kmer;
+    ///
+    /// assert_eq!(Kmer::from_literal("AA").rank(), 0);
+    /// assert_eq!(Kmer::from_literal("AC").rank(), 1);
+    /// assert_eq!(Kmer::from_literal("CA").rank(), 4);
+    /// assert_eq!(Kmer::from_rank(4, 2), Kmer::from_literal("CA"));
+    /// ```
+    pub fn rank(&self) -> u128 {
+        assert!(self.k <= 64, "the rank of a k-mer is only defined for k <= 64");
+        (0..self.k).fold(0, |rank, position| {
+            (rank << 2) | u128::from(LEXICAL_RANKS[self.index(position) as usize])
+        })
+    }
+
+    /// The k-mer of length `k` with the given rank, see `Kmer::rank`. Bits of `rank` beyond the
+    /// `2 * k` lowest ones are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > 64`.
+    pub fn from_rank(rank: u128, k: usize) -> Kmer {
+        assert!(k <= 64, "the rank of a k-mer is only defined for k <= 64");
+        let bases: Vec<u8> = (0..k)
+            .map(|position| b"ACGT"[((rank >> (2 * (k - 1 - position))) & 3) as usize])
+            .collect();
+        Kmer::new(k, &bases)
+    }
+}
+
+/// The 4 nucleotides encoded by each byte, first base first.
+static DECODE_TABLE: [[u8; 4]; 256] = {
+    let mut table = [[0; 4]; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let mut i = 0;
+        while i < 4 {
+            table[byte][i] = b"AGCT"[(byte >> (2 * i)) & 3];
+            i += 1;
+        }
+        byte += 1;
+    }
+    table
+};
+
+/// Decode the first `k` bases (at most) of the packed bytes `sequence`.
+fn decode_bytes(sequence: &[u8], k: usize) -> String {
+    let mut decoded: Vec<u8> = sequence
+        .iter()
+        .flat_map(|&byte| DECODE_TABLE[byte as usize].iter().copied())
+        .collect();
+    decoded.truncate(k);
+    String::from_utf8(decoded).unwrap()
+}
+
+/// Marks characters without a 2-bit code in `ACGT_CODES` and `NUCLEOTIDE_CODES`.
+const INVALID_CODE: u8 = 0xff;
+
+/// 2-bit code of each uppercase nucleotide, see `nuc_to_byte`.
+const ACGT_CODES: [u8; 256] = {
+    let mut codes = [INVALID_CODE; 256];
+    codes[b'A' as usize] = 0;
+    codes[b'G' as usize] = 1;
+    codes[b'C' as usize] = 2;
+    codes[b'T' as usize] = 3;
+    codes
+};
+
+/// 2-bit code of each nucleotide accepted by `Kmer::encode`: `ACGT_CODES`, with `U` read as `T`
+/// and lowercase read as uppercase.
+const NUCLEOTIDE_CODES: [u8; 256] = {
+    let mut codes = ACGT_CODES;
+    codes[b'U' as usize] = 3;
+    let mut upper = b'A';
+    while upper <= b'Z' {
+        codes[upper.to_ascii_lowercase() as usize] = codes[upper as usize];
+        upper += 1;
+    }
+    codes
+};
+
+/// Lexical rank of each 2-bit code: A, G, C, T are encoded as 0, 1, 2, 3.
+const LEXICAL_RANKS: [u8; 4] = [0, 2, 1, 3];
+
+/// Enumerate all 4^k k-mers of length `k` in lexicographic order, i.e. by `Kmer::rank`.
+///
+/// # Panics
+///
+/// Panics if `k > 63`. Although `Kmer::rank` is defined up to `k = 64`, the number of 64-mers,
+/// 2^128, does not fit into a `u128`.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::{all_kmers, Kmer};
+///
+/// let kmers: Vec<String> = all_kmers(1).map(|kmer| kmer.decode()).collect();
+/// assert_eq!(kmers, vec!["A", "C", "G", "T"]);
+/// ```
+pub fn all_kmers(k: usize) -> impl Iterator<Item = Kmer> {
+    assert!(k <= 63, "cannot enumerate all k-mers for k > 63");
+    (0..1u128 << (2 * k)).map(move |rank| Kmer::from_rank(rank, k))
+}
+
+impl fmt::Display for Kmer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sequence = decode_bytes(&self.sequence, self.k);
+        write!(f, "Vmer[{}]: {}", self.k, sequence)
+    }
+}
+
+//Iterator implementations
+
+impl Iterator for KmerIter {
+    type Item = u8;
+    fn next(&mut self) -> Option<u8> {
+        if self.position == self.kmer.k {
+            return None
+        }
+        self.nucleotide = self.kmer.index(self.position);
+        self.position += 1;
+        Some(self.nucleotide)
+    }
+}
+
+impl IntoIterator for Kmer {
+    type Item = u8;
+    type IntoIter = KmerIter;
+    fn into_iter(self) -> Self::IntoIter {
+        KmerIter {
+            kmer: self,
+            position: 0,
+            nucleotide: 0,
+        }
+    }
+}
+
+//BITWISE IMPLEMENTATIONS
+
+/// The encoded bytes of the k-mer with the unused bits of the last byte cleared.
+fn meaningful_bytes(kmer: &Kmer) -> impl Iterator<Item = u8> + '_ {
+    let used = kmer.k % 4;
+    let last = kmer.sequence.len().wrapping_sub(1);
+    kmer.sequence.iter().enumerate().map(move |(i, &byte)| {
+        if i == last && used != 0 {
+            byte & ((1 << (2 * used)) - 1)
+        } else {
+            byte
+        }
+    })
+}
+
+/// K-mers are equal if they have the same length and the same bases, regardless of the unused
+/// bits of the last encoded byte.
+impl PartialEq for Kmer {
+    fn eq(&self, other: &Self) -> bool {
+        self.k == other.k
+            && self.sequence.len() == other.sequence.len()
+            && meaningful_bytes(self).eq(meaningful_bytes(other))
+    }
+}
+
+impl Eq for Kmer {}
+
+impl Hash for Kmer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.k.hash(state);
+        self.sequence.len().hash(state);
+        for byte in meaningful_bytes(self) {
+            state.write_u8(byte);
+        }
+    }
+}
+
+/// K-mers are ordered lexicographically by their bases, with `A < C < G < T`. A k-mer that is a
+/// prefix of another one is the smaller of the two, e.g. `ACGT < ACGTA`.
+impl Ord for Kmer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let common = self.k.min(other.k);
+        for (i, (a, b)) in meaningful_bytes(self)
+            .zip(meaningful_bytes(other))
+            .enumerate()
+        {
+            if a == b {
+                continue;
+            }
+            for position in 4 * i..(4 * i + 4).min(common) {
+                let shift = 2 * (position % 4);
+                let (a, b) = ((a >> shift) & 3, (b >> shift) & 3);
+                if a != b {
+                    return LEXICAL_RANKS[a as usize].cmp(&LEXICAL_RANKS[b as usize]);
+                }
+            }
+            break;
+        }
+        self.k.cmp(&other.k)
+    }
+}
+
+impl PartialOrd for Kmer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Create a k-mer from a string of nucleotides, see `Kmer::from_literal`.
+///
+/// # Panics
+///
+/// Panics if the string contains a character that [`Kmer::new`] rejects.
+impl From<&str> for Kmer {
+    fn from(str_literal: &str) -> Self {
+        Kmer::from_literal(str_literal)
+    }
+}
+
+/// Create a k-mer from a slice of nucleotides, see `Kmer::try_new`.
+///
+/// # Example
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use bio::data_structures::kmer::Kmer;
+///
+/// assert_eq!(Kmer::try_from(&b"GATTACA"[..]), Ok(Kmer::from("GATTACA")));
+/// assert!(Kmer::try_from(&b"GATTNCA"[..]).is_err());
+/// ```
+impl TryFrom<&[u8]> for Kmer {
+    type Error = Error;
+
+    fn try_from(byte_seq: &[u8]) -> Result<Self> {
+        Kmer::try_new(byte_seq.len(), byte_seq)
+    }
+}
+
+/// Collect nucleotides into a k-mer, see `Kmer::push`.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::{Kmer, Nucleotide};
+///
+/// let kmer: Kmer = vec![Nucleotide::G, Nucleotide::A, Nucleotide::T].into_iter().collect();
+/// assert_eq!(kmer, Kmer::from_literal("GAT"));
+/// ```
+impl FromIterator<Nucleotide> for Kmer {
+    fn from_iter<I: IntoIterator<Item = Nucleotide>>(iter: I) -> Self {
+        let mut kmer = Kmer::with_capacity(0);
+        kmer.extend(iter);
+        kmer
+    }
+}
+
+/// Append nucleotides at the end of the k-mer, see `Kmer::push`.
+impl Extend<Nucleotide> for Kmer {
+    fn extend<I: IntoIterator<Item = Nucleotide>>(&mut self, iter: I) {
+        for nucleotide in iter {
+            self.push(nucleotide.to_char() as u8);
+        }
+    }
+}
+
+/// Combine the bytes of two k-mers of the same length with `op`.
+fn bitwise(lhs: &Kmer, rhs: &Kmer, op: impl Fn(u8, u8) -> u8) -> Kmer {
+    assert_eq!(lhs.k, rhs.k);
+    let mut sequence: Vec<u8> = Vec::with_capacity(lhs.sequence.len());
+    for (i, mer) in lhs.sequence.iter().enumerate() {
+        sequence.push(op(*mer, rhs.sequence[i]));
+    }
+    let mut kmer = Kmer { k: lhs.k, sequence };
+    kmer.mask_padding();
+    kmer
+}
+
+impl BitXor for Kmer {
+    type Output = Self;
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        &self ^ &rhs
+    }
+}
+
+impl<'a> BitXor<&'a Kmer> for &'a Kmer {
+    type Output = Kmer;
+    fn bitxor(self, rhs: &'a Kmer) -> Self::Output {
+        bitwise(self, rhs, |a, b| a ^ b)
+    }
+}
+
+impl BitAnd for Kmer {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        &self & &rhs
+    }
+}
+
+impl<'a> BitAnd<&'a Kmer> for &'a Kmer {
+    type Output = Kmer;
+    fn bitand(self, rhs: &'a Kmer) -> Self::Output {
+        bitwise(self, rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for Kmer {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        &self | &rhs
+    }
+}
+
+impl<'a> BitOr<&'a Kmer> for &'a Kmer {
+    type Output = Kmer;
+    fn bitor(self, rhs: &'a Kmer) -> Self::Output {
+        bitwise(self, rhs, |a, b| a | b)
+    }
+}
+
+impl Not for Kmer {
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        let mut not_sequence: Vec<u8> = Vec::new();
+        for mer in self.sequence.iter() {
+            not_sequence.push(!mer);
+        }
+        let mut kmer = Kmer {
+            k: self.k,
+            sequence: not_sequence,
+        };
+        kmer.mask_padding();
+        kmer
+    }
+}
+
+/// Estimate the strand bias between k-mers observed on the forward and on the reverse strand,
+/// e.g. in the reads of a stranded RNA-seq library.
+///
+/// Both sets are reduced to canonical k-mers. Each canonical k-mer seen only among the forward
+/// k-mers counts as forward, each one seen only among the reverse k-mers counts as reverse and
+/// one seen in both sets counts half to either side. The result is the forward fraction: values
+/// close to 0.5 indicate an unstranded signal, while values close to 1 (or 0) indicate that most
+/// k-mers are predominantly observed on the forward (or reverse) strand.
+/// If both sets are empty, 0.5 is returned.
+pub fn strand_bias(fwd_kmers: &HashSet<Kmer>, rev_kmers: &HashSet<Kmer>) -> f64 {
+    let fwd: HashSet<Kmer> = fwd_kmers.iter().map(|kmer| kmer.canonical()).collect();
+    let rev: HashSet<Kmer> = rev_kmers.iter().map(|kmer| kmer.canonical()).collect();
+    let total = fwd.union(&rev).count();
+    if total == 0 {
+        return 0.5;
+    }
+    let shared = fwd.intersection(&rev).count();
+    let fwd_only = fwd.len() - shared;
+    (fwd_only as f64 + 0.5 * shared as f64) / total as f64
+}
+
+/// Hash a k-mer by its length and its packed 2-bit representation. Like `PartialEq`, the unused
+/// bits of the last byte are ignored. Unlike the `Hash` implementation, the result does not
+/// depend on the platform or the hasher, so it can be used for sketches and indices that are
+/// persisted or compared between runs.
+pub(crate) fn hash_kmer(kmer: &Kmer) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write(&(kmer.k as u64).to_le_bytes());
+    for byte in meaningful_bytes(kmer) {
+        hasher.write_u8(byte);
+    }
+    hasher.finish()
+}
+
+/// Finalizer of the SplitMix64 generator, used to derive independent hash functions and to
+/// spread the bits of `hash_kmer`.
+pub(crate) fn mix64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Encode nucleotides read from `reader` into the packed 2-bit representation used by `Kmer`,
+/// without loading the entire input into memory. Whitespace, including line breaks of wrapped
+/// FASTA sequences, is skipped.
+///
+/// # Errors
+///
+/// Returns an error of kind `InvalidData` for a character that is neither whitespace nor a
+/// nucleotide accepted by [`Kmer::new`], and forwards errors of the reader.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::{encode_reader, Kmer};
+///
+/// let packed = encode_reader(&mut &b"ACGTA\nCG\n"[..]).unwrap();
+/// assert_eq!(packed, Kmer::from_literal("ACGTACG").sequence);
+/// ```
+pub fn encode_reader<R: io::Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut packed = Vec::new();
+    let mut buffer = [0u8; 8192];
+    let mut bits: u8 = 0;
+    let mut position = 0;
+    loop {
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        for &byte in &buffer[..n] {
+            if byte.is_ascii_whitespace() {
+                continue;
+            }
+            let code = try_nuc_to_byte(char::from(byte)).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    Error::InvalidNucleotide { byte, position }.to_string(),
+                )
+            })?;
+            bits |= code << (2 * (position % 4));
+            position += 1;
+            if position % 4 == 0 {
+                packed.push(bits);
+                bits = 0;
+            }
+        }
+    }
+    if position % 4 != 0 {
+        packed.push(bits);
+    }
+    Ok(packed)
+}
+
+//General utility function
+
+/// Convert a 2-bit code into its nucleotide.
+///
+/// # Panics
+///
+/// Panics if `byte` is not a valid code. See `try_byte_to_nuc` for a fallible version.
+pub fn byte_to_nuc(byte: u8) -> char {
+    try_byte_to_nuc(byte).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Convert a 2-bit code into its nucleotide.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidCode` if `byte` is not a valid code.
+pub fn try_byte_to_nuc(byte: u8) -> Result<char> {
+    match byte {
+        0 => Ok('A'),
+        1 => Ok('G'),
+        2 => Ok('C'),
+        3 => Ok('T'),
+        code => Err(Error::InvalidCode { code }),
+    }
+}
+
+/// Convert a nucleotide accepted by [`Kmer::new`] into its 2-bit code.
+///
+/// # Panics
+///
+/// Panics if `nuc` is any other character. See `try_nuc_to_byte` for a fallible version.
+pub fn nuc_to_byte(nuc: char) -> u8 {
+    try_nuc_to_byte(nuc).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Convert a nucleotide accepted by [`Kmer::new`] into its 2-bit code.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidSymbol` if `nuc` is any other character.
+pub fn try_nuc_to_byte(nuc: char) -> Result<u8> {
+    match nuc {
+        'A' | 'a' => Ok(0),
+        'G' | 'g' => Ok(1),
+        'C' | 'c' => Ok(2),
+        'T' | 't' | 'U' | 'u' => Ok(3),
+        symbol => Err(Error::InvalidSymbol { symbol }),
+    }
+}
+
+//TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::Kmer;
+    use super::Kmerizer;
+    use super::{nt_hash, NtHashKmerizer, Nucleotide};
+    use super::{all_kmers, dedup_consecutive, kmerize_joined, kmers_rev, kmers_with_pos};
+    use bio_types::strand::Strand;
+    use super::strand_bias;
+    use super::{encode_reader, nuc_to_byte, try_byte_to_nuc, try_nuc_to_byte, Error};
+    use std::io;
+    use crate::alphabets::dna;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+    use crate::data_structures::kmer::byte_to_nuc;
+    use std::path::Path;
+    use crate::io::fasta;
+
+
+    //TODO: WRITE SOME ACTUALLY GOOD UNIT TESTS
+    #[test]
+    fn test_vmer_instantiations() {
+        let _kmer_literal = Kmer::from_literal("ATGCATGCATGCATGCATGCATGC");
+        let sequence = String::from("AAAAATTTTTGGGGGCCCCC");
+        let k = 5;
+        for kmer in sequence.as_bytes().windows(k) {
+            let mut kmer1 = Kmer::new(k, kmer);
+            //let vmer2 = Vmer::new(k, kmer);
+            println!("Kmer:    {}", kmer1);
+            //println!("Rev:     {}", kmer1.retain_reverse());
+            kmer1.reverse_complement();
+            println!("RevComp: {}", kmer1);
+        }
+    }
+
+    #[test]
+    fn test_nucleotide_iterator() {
+        let kmer_literal = Kmer::from_literal("ATGCATGCATGCATGCATGCATGC");
+        println!("{}", kmer_literal);
+        for i in kmer_literal {
+            println!("{}", byte_to_nuc(i));
+        }
+    }
+
+    #[test]
+    fn test_kmerizer() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/debug.fasta");
+        let reader = fasta::Reader::from_file(path).unwrap();
+        let k = 21;
+        for record in reader.records() {
+            let record = record.unwrap();
+            let seq = record.seq();
+            let kmerizer = Kmerizer::new(k, seq);
+            assert_eq!(kmerizer.len(), seq.len().saturating_sub(k - 1));
+            let kmers: Vec<Kmer> = kmerizer.collect();
+            let expected: Vec<Kmer> = seq.windows(k).map(|w| Kmer::new(k, w)).collect();
+            assert_eq!(kmers, expected);
+        }
+
+        assert_eq!(Kmerizer::new(4, b"ACGT").count(), 1);
+        assert_eq!(Kmerizer::new(5, b"ACGT").count(), 0);
+        assert_eq!(Kmerizer::new(0, b"ACGT").count(), 0);
+        assert_eq!(Kmerizer::new(3, b"").count(), 0);
+    }
+
+    fn kmer_set(seq: &[u8], k: usize) -> HashSet<Kmer> {
+        seq.windows(k).map(|window| Kmer::new(k, window)).collect()
+    }
+
+    #[test]
+    fn test_canonical() {
+        let kmer = Kmer::from_literal("GGTAC");
+        let revcomp = Kmer::from_literal("GTACC");
+        assert_eq!(kmer.canonical(), revcomp.canonical());
+        assert_eq!(kmer.canonical(), kmer);
+        assert!(kmer.is_canonical());
+        assert!(!revcomp.is_canonical());
+
+        // palindromes are their own canonical form
+        let palindrome = Kmer::from_literal("ACGT");
+        assert_eq!(palindrome.canonical(), palindrome);
+        assert!(palindrome.is_canonical());
+
+        for seq in &["A", "T", "CAT", "TTTTTAAAC", "GATTACAGATTACA"] {
+            let kmer = Kmer::from_literal(seq);
+            let canonical = kmer.canonical();
+            assert!(canonical.is_canonical());
+            assert_eq!(canonical, kmer.make_reverse_complement().canonical());
+            assert!(canonical.decode() <= kmer.decode());
+        }
+    }
+
+    #[test]
+    fn test_kmers_rev() {
+        let seq = b"ATGCGTACGTTAGCATCGGATCGATCGTAGCTAG";
+        for k in 1..8 {
+            let forward: Vec<Kmer> = seq.windows(k).map(|w| Kmer::new(k, w)).collect();
+            let mut reversed = forward.clone();
+            reversed.reverse();
+            assert_eq!(kmers_rev(seq, k).collect::<Vec<Kmer>>(), reversed);
+            assert_eq!(kmers_rev(seq, k).rev().collect::<Vec<Kmer>>(), forward);
+        }
+
+        // consume from both ends
+        let mut kmers = kmers_rev(b"ACGTTA", 3);
+        assert_eq!(kmers.len(), 4);
+        assert_eq!(kmers.next(), Some(Kmer::from_literal("TTA")));
+        assert_eq!(kmers.next_back(), Some(Kmer::from_literal("ACG")));
+        assert_eq!(kmers.next_back(), Some(Kmer::from_literal("CGT")));
+        assert_eq!(kmers.next(), Some(Kmer::from_literal("GTT")));
+        assert_eq!(kmers.next(), None);
+        assert_eq!(kmers.next_back(), None);
+
+        assert_eq!(kmers_rev(b"AC", 3).count(), 0);
+        assert_eq!(kmers_rev(b"AC", 0).count(), 0);
+    }
+
+    #[test]
+    fn test_rank() {
+        for k in 0..6 {
+            let kmers: Vec<Kmer> = all_kmers(k).collect();
+            assert_eq!(kmers.len(), 1 << (2 * k));
+            for (i, kmer) in kmers.iter().enumerate() {
+                assert_eq!(kmer.rank(), i as u128);
+                assert_eq!(&Kmer::from_rank(kmer.rank(), k), kmer);
+            }
+            let mut sorted: Vec<String> = kmers.iter().map(|kmer| kmer.decode()).collect();
+            sorted.sort();
+            assert_eq!(
+                sorted,
+                kmers.iter().map(|kmer| kmer.decode()).collect::<Vec<String>>()
+            );
+        }
+
+        let long = Kmer::new(64, &[b'T'; 64]);
+        assert_eq!(long.rank(), u128::MAX);
+        assert_eq!(Kmer::from_rank(long.rank(), 64), long);
+        let kmer = Kmer::from_literal("GATTACAGATTACAGATTACAGATTACAGATTACAGATTACA");
+        assert_eq!(Kmer::from_rank(kmer.rank(), kmer.k), kmer);
+    }
+
+    #[test]
+    fn test_dedup_consecutive() {
+        let kmers: Vec<Kmer> = dedup_consecutive(Kmerizer::new(2, b"AAAAA")).collect();
+        assert_eq!(kmers, vec![Kmer::from_literal("AA")]);
+
+        let seq = b"AAAACAAAAGGG";
+        let kmers: Vec<String> = dedup_consecutive(seq.windows(3).map(|w| Kmer::new(3, w)))
+            .map(|kmer| kmer.decode())
+            .collect();
+        assert_eq!(
+            kmers,
+            vec!["AAA", "AAC", "ACA", "CAA", "AAA", "AAG", "AGG", "GGG"]
+        );
+        assert_eq!(dedup_consecutive(Vec::new()).count(), 0);
+    }
+
+    #[test]
+    fn test_from_raw() {
+        let kmer = Kmer::from_literal("GATTACA");
+        assert_eq!(Kmer::from_raw(7, kmer.sequence.clone()), Ok(kmer.clone()));
+        assert_eq!(Kmer::from_raw(0, Vec::new()), Ok(Kmer::from_literal("")));
+
+        assert_eq!(
+            Kmer::from_raw(7, vec![0]),
+            Err(Error::LengthMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert!(Kmer::from_raw(4, vec![0, 0]).is_err());
+
+        // the padding of the last byte is cleared
+        let mut dirty = kmer.sequence.clone();
+        dirty[1] |= 0b1100_0000;
+        let cleaned = Kmer::from_raw(7, dirty).unwrap();
+        assert_eq!(cleaned, kmer);
+        assert_eq!(cleaned.decode(), "GATTACA");
+    }
+
+    #[test]
+    fn test_base_counts() {
+        for literal in &["", "A", "GATTACA", "ACGT", "TTTTTTTT", "CCGCGAAAT"] {
+            let mut expected = [0; 4];
+            for nuc in literal.chars() {
+                expected[nuc_to_byte(nuc) as usize] += 1;
+            }
+            assert_eq!(Kmer::from_literal(literal).base_counts(), expected);
+        }
+    }
+
+    #[test]
+    fn test_kmerize_joined() {
+        let circular = kmerize_joined(&[b"GATTC"], 3, true);
+        assert_eq!(circular.len(), 5);
+        assert_eq!(circular[3], Kmer::from_literal("TCG"));
+        assert_eq!(circular[4], Kmer::from_literal("CGA"));
+
+        let linear = kmerize_joined(&[b"GATTC"], 3, false);
+        assert_eq!(linear, circular[..3].to_vec());
+
+        // k-mers span the junction between the sequences
+        let joined = kmerize_joined(&[b"GA", b"TT", b"C"], 3, false);
+        assert_eq!(joined, linear);
+
+        // a circular sequence shorter than k wraps around several times
+        let short = kmerize_joined(&[b"AC"], 5, true);
+        assert_eq!(
+            short,
+            vec![Kmer::from_literal("ACACA"), Kmer::from_literal("CACAC")]
+        );
+        assert!(kmerize_joined(&[], 3, true).is_empty());
+    }
+
+    #[test]
+    fn test_describe() {
+        let description = Kmer::from_literal("GATTACA").describe();
+        assert_eq!(description, "GATTACA (rc: TGTAATC)");
+        assert!(Kmer::from_literal("ACGT").describe().contains("ACGT (rc: ACGT)"));
+    }
+
+    #[test]
+    fn test_smallest_smer_position() {
+        let kmers = ["GATTACAGATTACA", "ACGTTGCATGTCGCATGATG", "AAAAAAAA", "ACGT"];
+        for literal in &kmers {
+            let kmer = Kmer::from_literal(literal);
+            for s in 1..=kmer.k {
+                let expected = (0..=kmer.k - s)
+                    .min_by_key(|&pos| Kmer::new(s, &literal.as_bytes()[pos..pos + s]).fold_to(64))
+                    .unwrap();
+                assert_eq!(kmer.smallest_smer_position(s), expected);
+            }
+        }
+        // ties are broken in favour of the leftmost s-mer
+        assert_eq!(Kmer::from_literal("AAAAAAAA").smallest_smer_position(3), 0);
+    }
+
+    #[test]
+    fn test_kmers_with_pos() {
+        let seq = b"ATGCGTACGTTAGCATCGG";
+        let k = 5;
+        let kmers: Vec<(usize, Kmer)> = kmers_with_pos(seq, k).collect();
+        assert_eq!(kmers.len(), seq.len() - k + 1);
+        for (i, (pos, kmer)) in kmers.into_iter().enumerate() {
+            assert_eq!(pos, i);
+            assert_eq!(kmer, Kmer::new(k, &seq[pos..pos + k]));
+        }
+        assert_eq!(kmers_with_pos(b"ACG", 4).count(), 0);
+    }
+
+    #[test]
+    fn test_is_rotation_of() {
+        let kmer = Kmer::from_literal("ACGT");
+        assert!(kmer.is_rotation_of(&Kmer::from_literal("GTAC")));
+        assert!(kmer.is_rotation_of(&kmer));
+        assert!(!kmer.is_rotation_of(&Kmer::from_literal("ACGA")));
+        assert!(!kmer.is_rotation_of(&Kmer::from_literal("ACG")));
+
+        let mut rotated = Kmer::from_literal("GATTACA");
+        for n in 0..7 {
+            rotated.rotate_left(n);
+            assert!(Kmer::from_literal("GATTACA").is_rotation_of(&rotated));
+        }
+    }
+
+    #[test]
+    fn test_from_acgt() {
+        let literals = ["", "A", "GATTACA", "ACGTACGT", "TTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTCG"];
+        for literal in &literals {
+            assert_eq!(Kmer::from_acgt(literal), Some(Kmer::from_literal(literal)));
+        }
+        assert_eq!(Kmer::from_acgt("gattaca"), None);
+        assert_eq!(Kmer::from_acgt("GATTaCA"), None);
+        assert_eq!(Kmer::from_acgt("ACGTN"), None);
+        assert_eq!(Kmer::from_acgt("ACGU"), None);
+    }
+
+    #[test]
+    fn test_make_canonical() {
+        for literal in &["GGTAC", "GTACC", "ACGT", "TTTTTTTAA", "AAC"] {
+            let kmer = Kmer::from_literal(literal);
+            let mut canonical = kmer.clone();
+            let strand = canonical.make_canonical();
+            assert_eq!(canonical, kmer.canonical());
+            let revcomp = Kmer::new(kmer.k, &dna::revcomp(literal.as_bytes()));
+            match strand {
+                Strand::Forward => assert!(kmer < revcomp),
+                Strand::Reverse => assert_eq!(canonical, revcomp),
+                Strand::Unknown => assert_eq!(kmer, revcomp),
+            }
+        }
+        let mut palindrome = Kmer::from_literal("ACGT");
+        assert!(matches!(palindrome.make_canonical(), Strand::Unknown));
+        let mut kmer = Kmer::from_literal("GTACC");
+        let expected = if Kmer::from_literal("GGTAC") < kmer {
+            Strand::Reverse
+        } else {
+            Strand::Forward
+        };
+        assert_eq!(kmer.make_canonical(), expected);
+    }
+
+    #[test]
+    fn test_strand_bias() {
+        let fwd = b"ATGCGTACGTTAGCATCGGATCGATCGTAGCTAGCATGCATCGATCGGCTAGCGCATTA";
+        let rev = b"TTGACCATGCAGGTACCAGTACGATCAGCATGGGTTTCCCAAAGGGCATCGA";
+        let k = 7;
+
+        // observed almost exclusively on the forward strand
+        let biased = strand_bias(&kmer_set(fwd, k), &kmer_set(&fwd[..10], k));
+        assert!(biased > 0.9);
+
+        // the same molecules observed on both strands
+        let unbiased = strand_bias(
+            &kmer_set(fwd, k).union(&kmer_set(rev, k)).cloned().collect(),
+            &kmer_set(&dna::revcomp(&fwd[..]), k)
+                .union(&kmer_set(&dna::revcomp(&rev[..]), k))
+                .cloned()
+                .collect(),
+        );
+        assert!((unbiased - 0.5).abs() < 0.05);
+
+        assert_eq!(strand_bias(&HashSet::new(), &HashSet::new()), 0.5);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut kmer = Kmer::from_literal("ACGTA");
+        kmer.rotate_left(2);
+        assert_eq!(kmer, Kmer::from_literal("GTAAC"));
+        kmer.rotate_right(2);
+        assert_eq!(kmer, Kmer::from_literal("ACGTA"));
+        kmer.rotate_right(1);
+        assert_eq!(kmer, Kmer::from_literal("AACGT"));
+
+        let original = Kmer::from_literal("ACGTACGTTGCA");
+        let mut kmer = original.clone();
+        kmer.rotate_left(original.k);
+        assert_eq!(kmer, original);
+        kmer.rotate_right(2 * original.k);
+        assert_eq!(kmer, original);
+        kmer.rotate_left(5);
+        assert_eq!(kmer.decode(), "CGTTGCAACGTA");
+    }
+
+    #[test]
+    fn test_try_encode() {
+        let mut kmer = Kmer::empty(6);
+        assert_eq!(
+            kmer.try_encode(b"ACGTNA"),
+            Err(Error::InvalidNucleotide {
+                byte: b'N',
+                position: 4
+            })
+        );
+        assert!(kmer.sequence.is_empty());
+        assert_eq!(kmer.try_encode(b"ACGTCA"), Ok(()));
+        assert_eq!(kmer, Kmer::from_literal("ACGTCA"));
+
+        // Encoding again replaces the contents, and a failed encoding keeps them.
+        assert_eq!(kmer.try_encode(b"TTGACC"), Ok(()));
+        assert_eq!(kmer, Kmer::from_literal("TTGACC"));
+        assert!(kmer.try_encode(b"TTGnCC").is_err());
+        assert_eq!(kmer, Kmer::from_literal("TTGACC"));
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        assert_eq!(
+            Kmer::try_new(3, b"ACGT"),
+            Err(Error::SequenceLengthMismatch { k: 3, found: 4 })
+        );
+        assert_eq!(
+            Kmer::try_new(5, b"ACGT"),
+            Err(Error::SequenceLengthMismatch { k: 5, found: 4 })
+        );
+        assert!(std::panic::catch_unwind(|| Kmer::new(3, b"ACGT")).is_err());
+
+        // encoding sets the length, such that index, decode and Display agree
+        let mut kmer = Kmer::with_capacity(3);
+        kmer.encode(b"ACGTA");
+        assert_eq!(kmer.k, 5);
+        assert_eq!(kmer.decode(), "ACGTA");
+        assert_eq!(kmer.to_string(), "Vmer[5]: ACGTA");
+        assert_eq!(kmer.index(4), nuc_to_byte('A'));
+        assert!(kmer.try_index(5).is_err());
+        kmer.encode(b"GG");
+        assert_eq!(kmer, Kmer::from_literal("GG"));
+    }
+
+    #[test]
+    fn test_reverse_complement() {
+        let mut kmer = Kmer::from_literal("GATTACAG");
+        let len = kmer.sequence.len();
+        kmer.reverse_complement();
+        assert_eq!(kmer.decode(), "CTGTAATC");
+        assert_eq!(kmer.sequence.len(), len);
+        kmer.reverse_complement();
+        assert_eq!(kmer.decode(), "GATTACAG");
+        assert_eq!(kmer.sequence.len(), len);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Kmer::try_new(5, b"ACGTC"), Ok(Kmer::from_literal("ACGTC")));
+        assert_eq!(
+            Kmer::try_new(5, b"ACNTC"),
+            Err(Error::InvalidNucleotide {
+                byte: b'N',
+                position: 2
+            })
+        );
+        assert_eq!(Kmer::try_from_literal("GATTACA"), Ok(Kmer::from_literal("GATTACA")));
+        assert_eq!(
+            Kmer::try_from_literal("-gattaca"),
+            Err(Error::InvalidNucleotide {
+                byte: b'-',
+                position: 0
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_encode_invalid() {
+        Kmer::from_literal("ACGTN");
+    }
+
+    #[test]
+    fn test_try_decode() {
+        let kmer = Kmer::from_literal("ACGTCA");
+        assert_eq!(kmer.try_decode(), Ok(String::from("ACGTCA")));
+
+        let truncated = Kmer {
+            k: 6,
+            sequence: vec![kmer.sequence[0]],
+        };
+        assert_eq!(
+            truncated.try_decode(),
+            Err(Error::LengthMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_complement_padding() {
+        let hash = |kmer: &Kmer| {
+            let mut hasher = DefaultHasher::new();
+            kmer.hash(&mut hasher);
+            hasher.finish()
+        };
+        for seq in &["A", "GATTACA", "ACGTAC", "ACGTACGT"] {
+            let expected = Kmer::from_literal(
+                std::str::from_utf8(&dna::complement_iupac(seq.as_bytes())).unwrap(),
+            );
+            let mut complemented = Kmer::from_literal(seq);
+            complemented.complement();
+            let negated = !Kmer::from_literal(seq);
+            for kmer in &[complemented, negated, Kmer::from_literal(seq).make_complement()] {
+                assert_eq!(kmer, &expected);
+                assert_eq!(hash(kmer), hash(&expected));
+            }
+
+            let revcomp =
+                Kmer::from_literal(std::str::from_utf8(&dna::revcomp(seq.as_bytes())).unwrap());
+            let mut reversed = Kmer::from_literal(seq);
+            reversed.reverse_complement();
+            assert_eq!(reversed, revcomp);
+            assert_eq!(hash(&reversed), hash(&revcomp));
+            assert_eq!(Kmer::from_literal(seq).make_reverse_complement(), revcomp);
+        }
+
+        let dirty = Kmer {
+            k: 3,
+            sequence: vec![0b1100_0000],
+        };
+        assert_eq!(dirty ^ Kmer::from_literal("AAA"), Kmer::from_literal("AAA"));
+    }
+
+    #[test]
+    fn test_ord() {
+        let kmers = ["ACGT", "ACGTA", "ACG", "T", "GATTACA", "CA", "AAAA", ""];
+        let mut sorted: Vec<Kmer> = kmers.iter().map(|seq| Kmer::from_literal(seq)).collect();
+        sorted.sort();
+        let decoded: Vec<String> = sorted.iter().map(|kmer| kmer.decode()).collect();
+        let mut expected: Vec<String> = kmers.iter().map(|seq| seq.to_string()).collect();
+        expected.sort();
+        assert_eq!(decoded, expected);
+
+        assert!(Kmer::from_literal("ACGT") < Kmer::from_literal("ACGTA"));
+        assert!(Kmer::from_literal("ACGTA") < Kmer::from_literal("ACGTC"));
+        // G is encoded as 1 and C as 2, but C sorts first
+        assert!(Kmer::from_literal("AC") < Kmer::from_literal("AG"));
+    }
+
+    #[test]
+    fn test_eq_ignores_padding() {
+        let hash = |kmer: &Kmer| {
+            let mut hasher = DefaultHasher::new();
+            kmer.hash(&mut hasher);
+            hasher.finish()
+        };
+        let clean = Kmer::from_literal("ACG");
+        let padded = Kmer {
+            k: 3,
+            sequence: vec![clean.sequence[0] | 0b1100_0000],
+        };
+        assert_eq!(clean, padded);
+        assert_eq!(hash(&clean), hash(&padded));
+        assert_eq!(clean.cmp(&padded), std::cmp::Ordering::Equal);
+        assert_ne!(clean, Kmer::from_literal("ACGA"));
+        assert_ne!(Kmer::from_literal("ACGT"), Kmer::from_literal("ACGTA"));
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        let kmer = |seq| Kmer::from_literal(seq);
+        assert_eq!(kmer("AAAA").hamming_distance(&kmer("AACA")), 1);
+        assert_eq!(kmer("AAAA").hamming_distance(&kmer("AAAA")), 0);
+        // A (00) vs T (11) flips both bits of a base
+        assert_eq!(kmer("ACGTA").hamming_distance(&kmer("TGCAT")), 5);
+        assert_eq!(kmer("").hamming_distance(&kmer("")), 0);
+
+        let padded = Kmer {
+            k: 3,
+            sequence: vec![kmer("ACG").sequence[0] | 0b1100_0000],
+        };
+        assert_eq!(padded.hamming_distance(&kmer("ACG")), 0);
+        assert_eq!(padded.hamming_distance(&kmer("ACC")), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hamming_distance_different_lengths() {
+        Kmer::from_literal("ACGT").hamming_distance(&Kmer::from_literal("ACG"));
+    }
+
+    #[test]
+    fn test_to_u64() {
+        let seq = b"ACGTTGCATGTCGCATGATGCATGAGAGCTAC";
+        for k in 0..=32 {
+            let kmer = Kmer::new(k, &seq[..k]);
+            let packed = kmer.to_u64().unwrap();
+            assert!(k == 32 || packed < 1 << (2 * k));
+            let unpacked = Kmer::from_u64(k, packed);
+            assert_eq!(unpacked.decode().as_bytes(), &seq[..k]);
+            assert_eq!(unpacked.sequence, kmer.sequence);
+        }
+        assert_eq!(Kmer::from_literal("T").to_u64(), Some(3));
+        assert_eq!(Kmer::from_literal("AG").to_u64(), Some(0b0100));
+        assert_eq!(Kmer::from_u64(2, u64::MAX), Kmer::from_literal("TT"));
+
+        let padded = Kmer {
+            k: 3,
+            sequence: vec![0b1100_0011],
+        };
+        assert_eq!(padded.to_u64(), Some(3));
+        assert_eq!(Kmer::new(33, &[b'A'; 33]).to_u64(), None);
+    }
+
+    #[test]
+    fn test_nt_hash_kmerizer() {
+        let seq = b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGTAGCTAGCTAGCATTACG".repeat(2);
+        let seq = &seq[..];
+        // rotations wrap around for k >= 64
+        for &k in &[1, 5, 21, 63, 64, 65, 100] {
+            let hashed: Vec<(Kmer, u64)> = NtHashKmerizer::new(k, seq).collect();
+            assert_eq!(hashed.len(), seq.len() - k + 1);
+            let expected: Vec<(Kmer, u64)> = seq
+                .windows(k)
+                .map(|window| (Kmer::new(k, window), nt_hash(window)))
+                .collect();
+            assert_eq!(hashed, expected);
+        }
+        assert_eq!(NtHashKmerizer::new(4, b"ACG").count(), 0);
+        assert_ne!(nt_hash(b"ACGT"), nt_hash(b"TGCA"));
+    }
+
+    #[test]
+    fn test_encode_all_bytes() {
+        // every byte is the encoding of exactly one 4-mer, with the first base in the low bits
+        for byte in 0..=255u8 {
+            let bases: Vec<u8> = (0..4)
+                .map(|i| byte_to_nuc((byte >> (2 * i)) & 3) as u8)
+                .collect();
+            assert_eq!(Kmer::new(4, &bases).sequence, vec![byte]);
+        }
+        assert_eq!(Kmer::from_literal("ACGT").sequence, vec![0b11_01_10_00]);
+        assert_eq!(Kmer::from_literal("GATTACA").sequence, vec![0b11_11_00_01, 0b00_10_00]);
+    }
+
+    #[test]
+    fn test_decode_all_bytes() {
+        for byte in 0..=255u8 {
+            let expected: String = (0..4).map(|i| byte_to_nuc((byte >> (2 * i)) & 3)).collect();
+            for k in 1..=4 {
+                let kmer = Kmer {
+                    k,
+                    sequence: vec![byte],
+                };
+                assert_eq!(kmer.decode(), expected[..k]);
+                assert_eq!(kmer.to_string(), format!("Vmer[{}]: {}", k, &expected[..k]));
+            }
+        }
+        assert_eq!(Kmer::from_literal("").decode(), "");
+        assert_eq!(
+            Kmer::from_literal("GATTACAGATTACA").decode(),
+            "GATTACAGATTACA"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        fn round_trip(kmer: &Kmer) -> std::result::Result<Kmer, csv::Error> {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(false)
+                .from_writer(vec![]);
+            writer.serialize(kmer)?;
+            let data = writer.into_inner().unwrap();
+            csv::ReaderBuilder::new()
+                .has_headers(false)
+                .from_reader(&data[..])
+                .deserialize()
+                .next()
+                .unwrap()
+        }
+
+        // CSV cannot tell an empty trailing sequence from a missing field, so "" is left out.
+        for seq in &["A", "GATTACA", "ACGTACGT"] {
+            let kmer = Kmer::from_literal(seq);
+            let decoded = round_trip(&kmer).unwrap();
+            assert_eq!(decoded, kmer);
+            assert_eq!(decoded.decode(), *seq);
+        }
+
+        // Set the padding bits behind the last base of "GATTACA".
+        let mut dirty = Kmer::from_literal("GATTACA");
+        dirty.sequence[1] |= 0b1100_0000;
+        let decoded = round_trip(&dirty).unwrap();
+        assert_eq!(decoded.sequence, Kmer::from_literal("GATTACA").sequence);
+        assert_eq!(decoded.decode(), "GATTACA");
+
+        // A sequence that does not hold `k` bases is rejected.
+        let mut too_long = Kmer::from_literal("A");
+        too_long.k = 100;
+        assert!(round_trip(&too_long).is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        use std::convert::TryInto;
+
+        let kmer: Kmer = "ACGT".into();
+        assert_eq!(kmer, Kmer::from_literal("ACGT"));
+        let converted: Result<Kmer, Error> = b"ACGT"[..].try_into();
+        assert_eq!(converted, Ok(kmer));
+        let converted: Result<Kmer, Error> = b"ACGn"[..].try_into();
+        assert_eq!(
+            converted,
+            Err(Error::InvalidNucleotide {
+                byte: b'n',
+                position: 3
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_invalid_str() {
+        let _: Kmer = "ACGN".into();
+    }
+
+    #[test]
+    fn test_push() {
+        let mut kmer = Kmer::from_literal("");
+        for &base in b"ACGT" {
+            kmer.push(base);
+        }
+        assert_eq!(kmer, Kmer::from_literal("ACGT"));
+        assert_eq!(kmer.sequence, Kmer::from_literal("ACGT").sequence);
+
+        let seq = b"GATTACAGATTACAT";
+        let mut kmer = Kmer::empty(0);
+        for (i, &base) in seq.iter().enumerate() {
+            kmer.push(base);
+            assert_eq!(kmer.sequence, Kmer::new(i + 1, &seq[..=i]).sequence);
+        }
+
+        assert_eq!(
+            kmer.try_push(b'n'),
+            Err(Error::InvalidNucleotide {
+                byte: b'n',
+                position: 15
+            })
+        );
+        assert_eq!(kmer.decode().as_bytes(), &seq[..]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_push_invalid() {
+        Kmer::from_literal("ACG").push(b'N');
+    }
+
+    #[test]
+    fn test_subsequence() {
+        assert_eq!(
+            Kmer::from_literal("ACGTACGT").subsequence(2, 6).decode(),
+            "GTAC"
+        );
+        let seq = "GATTACAGATTACATTTGCA";
+        let kmer = Kmer::from_literal(seq);
+        for start in 0..=seq.len() {
+            for end in start..=seq.len() {
+                let sub = kmer.subsequence(start, end);
+                assert_eq!(sub, Kmer::from_literal(&seq[start..end]));
+                assert_eq!(sub.sequence, Kmer::from_literal(&seq[start..end]).sequence);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_subsequence_out_of_bounds() {
+        Kmer::from_literal("ACGT").subsequence(2, 5);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut kmer = Kmer::from_literal("ACG");
+        kmer.append(&Kmer::from_literal("TTA"));
+        assert_eq!(kmer.decode(), "ACGTTA");
+
+        let seq = "GATTACAGATTACATTTGCA";
+        for split in 0..=seq.len() {
+            let (a, b) = (Kmer::from_literal(&seq[..split]), Kmer::from_literal(&seq[split..]));
+            let joined = a.concat(&b);
+            assert_eq!(joined.decode(), seq);
+            assert_eq!(joined.sequence, Kmer::from_literal(seq).sequence);
+        }
+    }
+
+    #[test]
+    fn test_gc_content() {
+        assert_eq!(Kmer::from_literal("GCGC").gc_content(), 1.0);
+        assert_eq!(Kmer::from_literal("ATAT").gc_content(), 0.0);
+        assert_eq!(Kmer::from_literal("ACGTT").gc_content(), 0.4);
+        assert_eq!(Kmer::from_literal("").gc_content(), 0.0);
+
+        // padding bits that would read as C are ignored
+        let padded = Kmer {
+            k: 3,
+            sequence: vec![0b1000_0000],
+        };
+        assert_eq!(padded.gc_content(), 0.0);
+    }
+
+    #[test]
+    fn test_neighbors_1mismatch() {
+        let kmer = Kmer::from_literal("AA");
+        let expected: Vec<Kmer> = ["CA", "GA", "TA", "AC", "AG", "AT"]
+            .iter()
+            .map(|seq| Kmer::from_literal(seq))
+            .collect();
+        assert_eq!(kmer.neighbors_1mismatch(), expected);
+
+        let kmer = Kmer::from_literal("GATTACAGC");
+        let neighbors = kmer.neighbors_1mismatch();
+        assert_eq!(neighbors.len(), 27);
+        assert_eq!(neighbors.iter().collect::<HashSet<_>>().len(), 27);
+        assert!(neighbors
+            .iter()
+            .all(|neighbor| neighbor.hamming_distance(&kmer) == 1));
+        assert!(Kmer::from_literal("").neighbors_1mismatch().is_empty());
+    }
+
+    #[test]
+    fn test_transcribe() {
+        assert_eq!(Kmer::from_literal("ATGC").transcribe(), "AUGC");
+        assert_eq!(Kmer::from_literal("AUGC"), Kmer::from_literal("ATGC"));
+        assert_eq!(Kmer::from_literal("AUGC").decode(), "ATGC");
+
+        let mut kmer = Kmer::from_literal("AUG");
+        kmer.push(b'U');
+        kmer.push_back(b'U');
+        assert_eq!(kmer.transcribe(), "UGUU");
+        assert_eq!(try_nuc_to_byte('U'), Ok(3));
+    }
+
+    #[test]
+    fn test_encode_lowercase() {
+        assert_eq!(Kmer::from_literal("acgt"), Kmer::from_literal("ACGT"));
+        assert_eq!(Kmer::from_literal("GATtaca").decode(), "GATTACA");
+        assert_eq!(Kmer::from_literal("augc").transcribe(), "AUGC");
+        let kmers: Vec<Kmer> = Kmerizer::new(3, b"acgTT").collect();
+        assert_eq!(kmers, Kmerizer::new(3, b"ACGTT").collect::<Vec<_>>());
+        assert!(Kmer::try_from_literal("acgn").is_err());
+    }
+
+    #[test]
+    fn test_nucleotide() {
+        let bases = [Nucleotide::A, Nucleotide::C, Nucleotide::G, Nucleotide::T];
+        for &base in &bases {
+            assert_eq!(Nucleotide::from_u8(base.to_u8()), Ok(base));
+            assert_eq!(try_nuc_to_byte(base.to_char()), Ok(base.to_u8()));
+            assert_eq!(base.complement().complement(), base);
+            assert_eq!(base.complement().to_u8(), 3 - base.to_u8());
+        }
+        assert_eq!(Nucleotide::from_u8(4), Err(Error::InvalidCode { code: 4 }));
+        assert!(bases.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let kmer = Kmer::from_literal("GATTACA");
+        let decoded: String = kmer.iter_nucleotides().map(Nucleotide::to_char).collect();
+        assert_eq!(decoded, "GATTACA");
+        let codes: Vec<u8> = kmer.iter_nucleotides().map(Nucleotide::to_u8).collect();
+        assert_eq!(codes, kmer.into_iter().collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_bitand_bitor() {
+        let a = Kmer::from_literal("GATTACA");
+        let b = Kmer::from_literal("CTGACCA");
+        // A = 00, G = 01, C = 10, T = 11
+        assert_eq!(a.clone() & b.clone(), Kmer::from_literal("AAGAACA"));
+        assert_eq!(a.clone() | b.clone(), Kmer::from_literal("TTTTCCA"));
+
+        // the operands carry different garbage in the unused bits of their last byte
+        let mut dirty_a = a.clone();
+        *dirty_a.sequence.last_mut().unwrap() |= 0b1100_0000;
+        let mut dirty_b = b.clone();
+        *dirty_b.sequence.last_mut().unwrap() |= 0b0100_0000;
+        let and = dirty_a.clone() & dirty_b.clone();
+        let or = dirty_a | dirty_b;
+        assert_eq!(and, a.clone() & b.clone());
+        assert_eq!(or, a.clone() | b.clone());
+        // the result is masked, not merely compared without the padding
+        assert_eq!(and.sequence, (a.clone() & b.clone()).sequence);
+        assert_eq!(or.sequence, (a | b).sequence);
+    }
+
+    #[test]
+    fn test_hash_kmer_ignores_padding() {
+        let clean = Kmer::from_literal("GATTACA");
+        let mut dirty = clean.clone();
+        *dirty.sequence.last_mut().unwrap() |= 0b1100_0000;
+        assert_eq!(dirty, clean);
+        assert_eq!(super::hash_kmer(&dirty), super::hash_kmer(&clean));
+        assert_eq!(dirty.fold_to(64), clean.fold_to(64));
+
+        // the same packed bytes, but different lengths
+        let short = Kmer::from_literal("AAA");
+        let long = Kmer::from_literal("AAAA");
+        assert_eq!(short.sequence, long.sequence);
+        assert_ne!(super::hash_kmer(&short), super::hash_kmer(&long));
+    }
+
+    #[test]
+    fn test_from_iter_nucleotides() {
+        let nucleotides = vec![
+            Nucleotide::G,
+            Nucleotide::A,
+            Nucleotide::T,
+            Nucleotide::T,
+            Nucleotide::A,
+            Nucleotide::C,
+            Nucleotide::A,
+        ];
+        let kmer: Kmer = nucleotides.iter().cloned().collect();
+        assert_eq!(kmer, Kmer::from_literal("GATTACA"));
+        assert_eq!(kmer.iter_nucleotides().collect::<Vec<_>>(), nucleotides);
+        assert_eq!(
+            Kmer::from_literal("GATTACA")
+                .iter_nucleotides()
+                .map(Nucleotide::complement)
+                .collect::<Kmer>(),
+            Kmer::from_literal("CTAATGT")
+        );
+        assert!(Vec::new().into_iter().collect::<Kmer>().is_empty());
+
+        let mut kmer = Kmer::from_literal("GAT");
+        kmer.extend(vec![Nucleotide::T, Nucleotide::A, Nucleotide::C, Nucleotide::A]);
+        assert_eq!(kmer, Kmer::from_literal("GATTACA"));
+    }
+
+    #[test]
+    fn test_len() {
+        let kmer = Kmer::from_literal("GATTACA");
+        assert_eq!(kmer.len(), 7);
+        assert!(!kmer.is_empty());
+
+        let empty = Kmer::from_literal("");
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+        assert!(empty.sequence.is_empty());
+        assert_eq!(empty.decode(), "");
+        assert_eq!(empty.to_string(), "Vmer[0]: ");
+        let mut kmer = Kmer::with_capacity(0);
+        kmer.encode(b"");
+        assert_eq!(kmer, empty);
+    }
+
+    #[test]
+    fn test_reverse() {
+        for seq in &["ACGT", "GATTACA", "ACCGTAGGCTGACCG", "T", ""] {
+            let mut kmer = Kmer::from_literal(seq);
+            let reversed: String = seq.chars().rev().collect();
+            assert_eq!(kmer.make_reverse().decode(), reversed);
+            assert_eq!(kmer.make_reverse().make_reverse(), kmer);
+            kmer.reverse();
+            assert_eq!(kmer, Kmer::from_literal(&reversed));
+        }
+        let kmer = Kmer::from_literal("ACGT");
+        assert_eq!(kmer.make_reverse().decode(), "TGCA");
+        // ACGT is its own reverse complement, but not its own reverse
+        assert_eq!(kmer.make_reverse_complement(), kmer);
+        assert_ne!(kmer.make_reverse(), kmer.make_reverse_complement());
+        assert_eq!(kmer.make_reverse(), kmer.make_complement());
+    }
+
+    #[test]
+    fn test_prefix_suffix() {
+        let acgt = Kmer::from_literal("ACGT");
+        let cgta = Kmer::from_literal("CGTA");
+        assert_eq!(acgt.prefix(), Kmer::from_literal("ACG"));
+        assert_eq!(acgt.suffix(), Kmer::from_literal("CGT"));
+        assert_eq!(cgta.prefix(), Kmer::from_literal("CGT"));
+        assert_eq!(cgta.suffix(), Kmer::from_literal("GTA"));
+        assert!(acgt.overlaps(&cgta));
+        assert!(!cgta.overlaps(&acgt));
+        assert!(!acgt.overlaps(&acgt));
+
+        let long = Kmer::from_literal("GATTACAGATTACA");
+        assert_eq!(long.prefix().decode(), "GATTACAGATTAC");
+        assert_eq!(long.suffix().decode(), "ATTACAGATTACA");
+        assert_eq!(Kmer::from_literal("T").suffix(), Kmer::from_literal(""));
+    }
+
+    #[test]
+    fn test_bitxor_self() {
+        for seq in &["GATTACA", "TTTTT", "CGTA", ""] {
+            let kmer = Kmer::from_literal(seq);
+            let xor = &kmer ^ &kmer;
+            assert_eq!(xor.k, kmer.k);
+            assert_eq!(xor, Kmer::new(kmer.k, "A".repeat(kmer.k).as_bytes()));
+            assert!(xor.sequence.iter().all(|&byte| byte == 0));
+        }
+    }
+
+    #[test]
+    fn test_bitwise_by_reference() {
+        let a = Kmer::from_literal("GATTACA");
+        let b = Kmer::from_literal("CTGACCA");
+        assert_eq!(&a ^ &b, a.clone() ^ b.clone());
+        assert_eq!(&a & &b, a.clone() & b.clone());
+        assert_eq!(&a | &b, a.clone() | b.clone());
+        // the operands are still usable
+        assert_eq!(a.decode(), "GATTACA");
+        assert_eq!(b.decode(), "CTGACCA");
+        assert_eq!(&(&a ^ &b) ^ &b, a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bitand_different_lengths() {
+        let _ = Kmer::from_literal("ACGT") & Kmer::from_literal("ACG");
+    }
+
+    #[test]
+    fn test_index() {
+        let kmer = Kmer::from_literal("ACGTC");
+        assert_eq!(kmer.index(0), nuc_to_byte('A'));
+        assert_eq!(kmer.index(4), nuc_to_byte('C'));
+    }
+
+    #[test]
+    #[should_panic(expected = "position 5 is out of bounds for a k-mer of length 5")]
+    fn test_index_out_of_bounds() {
+        Kmer::from_literal("ACGTC").index(5);
+    }
+
+    #[test]
+    fn test_try_index() {
+        let kmer = Kmer::from_literal("ACGTC");
+        assert_eq!(kmer.try_index(4), Ok(nuc_to_byte('C')));
+        assert_eq!(
+            kmer.try_index(5),
+            Err(Error::IndexOutOfBounds {
+                position: 5,
+                len: 5
+            })
+        );
+        let truncated = Kmer {
+            k: 5,
+            sequence: vec![kmer.sequence[0]],
+        };
+        assert_eq!(
+            truncated.try_index(4),
+            Err(Error::LengthMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_byte_to_nuc() {
+        assert_eq!(try_byte_to_nuc(2), Ok('C'));
+        assert_eq!(try_byte_to_nuc(4), Err(Error::InvalidCode { code: 4 }));
+    }
+
+    #[test]
+    fn test_try_nuc_to_byte() {
+        assert_eq!(try_nuc_to_byte('T'), Ok(3));
+        assert_eq!(
+            try_nuc_to_byte('N'),
+            Err(Error::InvalidSymbol { symbol: 'N' })
+        );
+    }
+
+    #[test]
+    fn test_project() {
+        assert_eq!(
+            Kmer::project(b"ACGTACG", &[0, 2, 4]),
+            Kmer::from_literal("AGA")
+        );
+        assert_eq!(
+            Kmer::project(b"ACGTACG", &[6, 0, 1, 3, 5]).decode(),
+            "GACTC"
+        );
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut kmer = Kmer::with_capacity(21);
+        let capacity = kmer.sequence.capacity();
+        assert!(capacity >= 6);
+        kmer.encode(b"ACGTACGTACGTACGTACGTA");
+        assert_eq!(kmer.sequence.capacity(), capacity);
+        assert_eq!(kmer, Kmer::from_literal("ACGTACGTACGTACGTACGTA"));
+    }
+
+    #[test]
+    fn test_decode_wrapped() {
+        let seq = "ACGTTGCA".repeat(13);
+        let kmer = Kmer::new(100, &seq.as_bytes()[..100]);
+        let wrapped = kmer.decode_wrapped(60);
+        assert_eq!(wrapped.as_bytes()[60], b'\n');
+        assert_eq!(wrapped.replace('\n', ""), kmer.decode());
+        assert_eq!(wrapped.lines().map(|line| line.len()).collect::<Vec<_>>(), vec![60, 40]);
+        assert_eq!(Kmer::from_literal("ACGT").decode_wrapped(2), "AC\nGT");
+
+        let mut out = Vec::new();
+        kmer.write_wrapped(&mut out, 60).unwrap();
+        assert_eq!(out, format!("{}\n", wrapped).into_bytes());
+    }
+
+    #[test]
+    fn test_push_back() {
+        let seq = b"ACGTTGCATGTCGCATGATGCA";
+        for &k in &[1, 3, 4, 5, 8] {
+            let mut kmer = Kmer::new(k, &seq[..k]);
+            for (i, &nucleotide) in seq[k..].iter().enumerate() {
+                kmer.push_back(nucleotide);
+                assert_eq!(kmer, Kmer::new(k, &seq[i + 1..i + 1 + k]));
+            }
+        }
+
+        // sliding happens in place
+        let mut kmer = Kmer::new(5, &seq[..5]);
+        let buffer = kmer.sequence.as_ptr();
+        for &nucleotide in &seq[5..] {
+            kmer.push_back(nucleotide);
+        }
+        assert_eq!(kmer.sequence.as_ptr(), buffer);
+
+        // stray padding bits do not leak into the appended base
+        let mut padded = Kmer {
+            k: 3,
+            sequence: vec![Kmer::from_literal("ACG").sequence[0] | 0b1100_0000],
+        };
+        padded.push_back(b'A');
+        assert_eq!(padded, Kmer::from_literal("CGA"));
+    }
+
+    /// A reader handing out its data in chunks of at most `chunk` bytes.
+    struct ChunkedReader<'a> {
+        data: &'a [u8],
+        chunk: usize,
+    }
+
+    impl<'a> io::Read for ChunkedReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.chunk.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_encode_reader() {
+        let seq = b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGTAGCTAGCTAGC";
+        let wrapped: Vec<u8> = seq
+            .chunks(7)
+            .flat_map(|line| line.iter().chain(b"\n").cloned())
+            .collect();
+        let expected = Kmer::new(seq.len(), seq).sequence;
+        for &chunk in &[1, 2, 3, 5, 64] {
+            let mut reader = ChunkedReader {
+                data: &wrapped,
+                chunk,
+            };
+            assert_eq!(encode_reader(&mut reader).unwrap(), expected);
+        }
+
+        let err = encode_reader(&mut &b"ACGT\nACNT"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(encode_reader(&mut &b""[..]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_push_front() {
+        let seq = b"ACGTTGCATGTCGCATGATGCA";
+        for &k in &[1, 3, 4, 5, 8] {
+            let start = seq.len() - k;
+            let mut kmer = Kmer::new(k, &seq[start..]);
+            for i in (0..start).rev() {
+                kmer.push_front(seq[i]);
+                assert_eq!(kmer, Kmer::new(k, &seq[i..i + k]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_successors_predecessors() {
+        let kmer = Kmer::from_literal("ACGTTGCAT");
+        let decoded = kmer.decode();
+        let successors = kmer.successors();
+        for (successor, base) in successors.iter().zip("ACGT".chars()) {
+            let successor = successor.decode();
+            assert_eq!(&successor[..8], &decoded[1..]);
+            assert!(successor.ends_with(base));
+        }
+        for (predecessor, base) in kmer.predecessors().iter().zip("ACGT".chars()) {
+            let predecessor = predecessor.decode();
+            assert_eq!(&predecessor[1..], &decoded[..8]);
+            assert!(predecessor.starts_with(base));
+        }
+        assert!(successors[3]
+            .predecessors()
+            .iter()
+            .any(|predecessor| predecessor == &kmer));
+    }
+}
@@ -0,0 +1,657 @@
+//! Compact sketches of the k-mer content of DNA sequences.
+
+use std::collections::{HashMap, HashSet};
+
+use bv::{BitVec, Bits, BitsMut};
+
+use crate::data_structures::kmer::{hash_kmer, mix64, Kmer};
+
+/// Compute a binary fingerprint of length `bits` of the k-mer content of `seq`: the hash of each
+/// k-mer is folded into the fingerprint by setting bit `hash % bits`. Different k-mers may
+/// collide on the same bit, which becomes more likely the smaller `bits` is.
+///
+/// # Panics
+///
+/// Panics if `bits` is zero or `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::sketch::{fingerprint, tanimoto};
+///
+/// let a = fingerprint(b"ACGTTGCATGTCGCATGATGCATGAG", 5, 256);
+/// let b = fingerprint(b"ACGTTGCATGTCGCATGATGCATGAG", 5, 256);
+/// assert_eq!(tanimoto(&a, &b), 1.0);
+/// ```
+pub fn fingerprint(seq: &[u8], k: usize, bits: u64) -> BitVec<u64> {
+    assert!(bits > 0, "fingerprint must have at least one bit");
+    let mut fingerprint = BitVec::new_fill(false, bits);
+    if k == 0 {
+        return fingerprint;
+    }
+    for window in seq.windows(k) {
+        fingerprint.set_bit(hash_kmer(&Kmer::new(k, window)) % bits, true);
+    }
+    fingerprint
+}
+
+/// Count the set bits of `a` combined with `b` block-wise by `op`.
+fn count_ones(a: &BitVec<u64>, b: &BitVec<u64>, op: fn(u64, u64) -> u64) -> u64 {
+    (0..a.block_len())
+        .map(|i| u64::from(op(a.get_block(i), b.get_block(i)).count_ones()))
+        .sum()
+}
+
+/// Tanimoto coefficient of two binary fingerprints, i.e. the number of bits set in both divided
+/// by the number of bits set in either. Two empty fingerprints have a coefficient of 1.
+///
+/// # Panics
+///
+/// Panics if the fingerprints differ in length.
+pub fn tanimoto(a: &BitVec<u64>, b: &BitVec<u64>) -> f64 {
+    assert_eq!(a.len(), b.len(), "fingerprints must have the same length");
+    let union = count_ones(a, b, |x, y| x | y);
+    if union == 0 {
+        return 1.0;
+    }
+    count_ones(a, b, |x, y| x & y) as f64 / union as f64
+}
+
+/// Compute a feature vector of length `dim` of the k-mer content of `seq` with the hashing
+/// trick: each k-mer is hashed to one of `dim` buckets, whose count is incremented. The result
+/// is a dense, fixed-length vector that can be fed to linear models without building a table of
+/// all k-mers. The vector sums to the number of k-mers of `seq`.
+///
+/// # Panics
+///
+/// Panics if `dim` is zero or `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::sketch::hashed_kmer_features;
+///
+/// let features = hashed_kmer_features(b"ACGTTGCA", 3, 16);
+/// assert_eq!(features.len(), 16);
+/// assert_eq!(features.iter().sum::<f32>(), 6.0);
+/// ```
+pub fn hashed_kmer_features(seq: &[u8], k: usize, dim: usize) -> Vec<f32> {
+    kmer_features(seq, k, dim, false)
+}
+
+/// Like `hashed_kmer_features`, but each k-mer is added with a sign of +1 or -1 derived from
+/// an independent hash. Collisions then cancel out in expectation instead of biasing the
+/// buckets upwards, so inner products between feature vectors are unbiased.
+///
+/// # Panics
+///
+/// Panics if `dim` is zero or `seq` contains a character that [`Kmer::new`] rejects.
+pub fn signed_hashed_kmer_features(seq: &[u8], k: usize, dim: usize) -> Vec<f32> {
+    kmer_features(seq, k, dim, true)
+}
+
+fn kmer_features(seq: &[u8], k: usize, dim: usize, signed: bool) -> Vec<f32> {
+    assert!(dim > 0, "feature vector must have at least one dimension");
+    let mut features = vec![0.0; dim];
+    if k == 0 {
+        return features;
+    }
+    for window in seq.windows(k) {
+        let hash = hash_kmer(&Kmer::new(k, window));
+        let sign = if signed && mix64(hash) & 1 == 1 {
+            -1.0
+        } else {
+            1.0
+        };
+        features[(hash % dim as u64) as usize] += sign;
+    }
+    features
+}
+
+/// Estimate the fraction of identical bases between `a` and `b` from their shared canonical
+/// k-mers, without aligning them. The Jaccard similarity `j` of both k-mer sets is turned into
+/// the Mash distance `-ln(2j / (1 + j)) / k`, assuming substitutions occur independently at
+/// each position, and the identity is one minus that distance (clamped to `[0, 1]`).
+///
+/// # Panics
+///
+/// Panics if `k` is zero or a sequence contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::sketch::estimate_identity;
+///
+/// let read = b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCG";
+/// assert_eq!(estimate_identity(read, read, 11), 1.0);
+/// ```
+pub fn estimate_identity(a: &[u8], b: &[u8], k: usize) -> f64 {
+    assert!(k > 0, "k must be positive");
+    let kmers = |seq: &[u8]| -> HashSet<u64> {
+        seq.windows(k)
+            .map(|window| hash_kmer(&Kmer::new(k, window).canonical()))
+            .collect()
+    };
+    let (a, b) = (kmers(a), kmers(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    let jaccard = a.intersection(&b).count() as f64 / union as f64;
+    if jaccard == 0.0 {
+        return 0.0;
+    }
+    let distance = -(2.0 * jaccard / (1.0 + jaccard)).ln() / k as f64;
+    (1.0 - distance).clamp(0.0, 1.0)
+}
+
+/// A set of k-mers stored as a table of `2^bits` presence bits, addressed by `Kmer::fold_to`.
+/// Distinct k-mers may fold to the same bit, so `contains` can report false positives (with a
+/// probability of about `1 - exp(-n / 2^bits)` after inserting `n` distinct k-mers), but never
+/// false negatives.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::sketch::FoldedKmerSet;
+/// use bio::data_structures::kmer::Kmer;
+///
+/// let mut set = FoldedKmerSet::new(16);
+/// set.insert(&Kmer::from_literal("GATTACA"));
+/// assert!(set.contains(&Kmer::from_literal("GATTACA")));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FoldedKmerSet {
+    bits: u32,
+    table: BitVec<u64>,
+}
+
+impl FoldedKmerSet {
+    /// Create an empty set with a table of `2^bits` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is larger than 48.
+    pub fn new(bits: u32) -> Self {
+        assert!(bits <= 48, "a folded k-mer set has at most 2^48 bits");
+        FoldedKmerSet {
+            bits,
+            table: BitVec::new_fill(false, 1 << bits),
+        }
+    }
+
+    /// The number of address bits of the table.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn insert(&mut self, kmer: &Kmer) {
+        self.table.set_bit(kmer.fold_to(self.bits), true);
+    }
+
+    /// Whether `kmer` may have been inserted. False positives are possible, false negatives
+    /// are not.
+    pub fn contains(&self, kmer: &Kmer) -> bool {
+        self.table.get_bit(kmer.fold_to(self.bits))
+    }
+}
+
+/// A bottom-n MinHash sketch of a sequence: the `n` smallest hashes of its canonical k-mers, such
+/// that a sequence and its reverse complement have the same sketch. Comparing the sketches of two
+/// sequences estimates the similarity of their k-mer sets at a fraction of the cost.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::sketch::MinHashSketch;
+///
+/// let seq = b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGTAGCTAGC";
+/// let sketch = MinHashSketch::from_sequence(seq, 11, 16);
+/// assert_eq!(sketch.len(), 16);
+/// assert_eq!(sketch.jaccard(&sketch), 1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinHashSketch {
+    k: usize,
+    n: usize,
+    hashes: Vec<u64>,
+}
+
+impl MinHashSketch {
+    /// Sketch the canonical k-mers of `seq`, keeping the `n` smallest distinct hashes (see
+    /// `Kmer::fold_to`). Use `usize::MAX` as `n` to keep all of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn from_sequence(seq: &[u8], k: usize, n: usize) -> Self {
+        let mut hashes: Vec<u64> = if k == 0 {
+            Vec::new()
+        } else {
+            seq.windows(k)
+                .map(|window| Kmer::new(k, window).canonical().fold_to(64))
+                .collect()
+        };
+        hashes.sort_unstable();
+        hashes.dedup();
+        hashes.truncate(n);
+        MinHashSketch { k, n, hashes }
+    }
+
+    /// The length of the sketched k-mers.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The largest number of hashes kept by the sketch.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// The hashes of the sketch, in ascending order.
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes
+    }
+
+    /// The number of hashes in the sketch, which is smaller than `n` for sequences with fewer
+    /// than `n` distinct k-mers.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Estimate the Jaccard similarity of the k-mer sets of both sketched sequences: the
+    /// fraction of the smallest `n` hashes of the union of both sketches that occur in both.
+    /// Two empty sketches have a similarity of 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sketches were built with different `k`.
+    pub fn jaccard(&self, other: &MinHashSketch) -> f64 {
+        assert_eq!(self.k, other.k, "sketches must use the same k");
+        let n = self.n.min(other.n);
+        let (mut a, mut b) = (self.hashes.iter().peekable(), other.hashes.iter().peekable());
+        let (mut union, mut shared) = (0, 0);
+        while union < n {
+            match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x == y => {
+                    shared += 1;
+                    a.next();
+                    b.next();
+                }
+                (Some(x), Some(y)) if x < y => {
+                    a.next();
+                }
+                (Some(_), Some(_)) => {
+                    b.next();
+                }
+                (Some(_), None) => {
+                    a.next();
+                }
+                (None, Some(_)) => {
+                    b.next();
+                }
+                (None, None) => break,
+            }
+            union += 1;
+        }
+        if union == 0 {
+            return 1.0;
+        }
+        shared as f64 / union as f64
+    }
+
+    /// Estimate the containment of the k-mers of the sequence sketched by `self` in those of
+    /// the sequence sketched by `other`, i.e. the fraction of the former that also occur in the
+    /// latter. Only hashes up to the largest one retained by both sketches are compared, so
+    /// sketching a short sequence (e.g. a read) with all of its hashes and a long one with few
+    /// still yields an unbiased estimate. Returns 0 if no hashes can be compared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sketches were built with different `k`.
+    pub fn containment(&self, other: &MinHashSketch) -> f64 {
+        assert_eq!(self.k, other.k, "sketches must use the same k");
+        let max_hash = |sketch: &MinHashSketch| match sketch.hashes.last() {
+            Some(&max) if sketch.hashes.len() == sketch.n => max,
+            _ => u64::MAX,
+        };
+        let max = max_hash(self).min(max_hash(other));
+        let sample: Vec<u64> = self
+            .hashes
+            .iter()
+            .cloned()
+            .take_while(|&hash| hash <= max)
+            .collect();
+        if sample.is_empty() {
+            return 0.0;
+        }
+        let contained = sample
+            .iter()
+            .filter(|hash| other.hashes.binary_search(hash).is_ok())
+            .count();
+        contained as f64 / sample.len() as f64
+    }
+}
+
+/// Classify `read` by the reference whose k-mers contain the largest fraction of its own (see
+/// `MinHashSketch::containment`). Returns the label of that reference if the containment is
+/// at least `threshold`, and `None` otherwise.
+///
+/// # Panics
+///
+/// Panics if a reference sketch was not built with k-mers of length `k`, or if `read`
+/// contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::sketch::{classify, MinHashSketch};
+///
+/// let references = vec![
+///     ("a", MinHashSketch::from_sequence(b"ACGTTGCATGTCGCATGATGCATGAGAGCTACG", 9, 100)),
+///     ("b", MinHashSketch::from_sequence(b"GGGTTTCCCAAAGGGTTTCCCAAATTTGCGCGC", 9, 100)),
+/// ];
+/// assert_eq!(classify(b"TCGCATGATGCATGAGA", &references, 9, 0.8), Some("a"));
+/// ```
+pub fn classify<'a>(
+    read: &[u8],
+    references: &'a [(&'a str, MinHashSketch)],
+    k: usize,
+    threshold: f64,
+) -> Option<&'a str> {
+    let sketch = MinHashSketch::from_sequence(read, k, usize::MAX);
+    let mut best = None;
+    for (label, reference) in references {
+        let containment = sketch.containment(reference);
+        let better = match best {
+            Some((_, max)) => containment > max,
+            None => true,
+        };
+        if containment >= threshold && better {
+            best = Some((*label, containment));
+        }
+    }
+    best.map(|(label, _)| label)
+}
+
+/// Length of the MinHash signatures used by `similarity_join`.
+const SIGNATURE_LEN: usize = 128;
+
+/// MinHash signature of the canonical k-mers of `seq`: the minimum of each of `SIGNATURE_LEN`
+/// hash functions over all k-mers.
+fn signature(seq: &[u8], k: usize) -> Vec<u64> {
+    let mut signature = vec![u64::MAX; SIGNATURE_LEN];
+    if k == 0 {
+        return signature;
+    }
+    for window in seq.windows(k) {
+        let hash = hash_kmer(&Kmer::new(k, window).canonical());
+        for (i, min) in signature.iter_mut().enumerate() {
+            *min = (*min).min(mix64(hash ^ mix64(i as u64 + 1)));
+        }
+    }
+    signature
+}
+
+/// Minhash estimate of the Jaccard similarity: the fraction of agreeing signature entries.
+fn signature_similarity(a: &[u64], b: &[u64]) -> f64 {
+    a.iter().zip(b).filter(|(x, y)| x == y).count() as f64 / a.len() as f64
+}
+
+/// Find all pairs of sequences whose k-mer sets have an estimated Jaccard similarity of at least
+/// `threshold`. Returns `(i, j, similarity)` triples with `i < j` indexing into `seqs`, sorted by
+/// `i` and `j`.
+///
+/// Each sequence is summarized by a MinHash signature of its canonical k-mers (so both strands
+/// of a sequence are considered equal). To avoid comparing all pairs, the signatures are split
+/// into bands and only sequences that agree on all entries of at least one band are compared
+/// (locality sensitive hashing). The number of bands is chosen such that pairs with a
+/// similarity around `threshold` are very likely to become candidates, so few similar pairs are
+/// missed while dissimilar pairs are rarely compared.
+///
+/// # Panics
+///
+/// Panics if a sequence contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::sketch::similarity_join;
+///
+/// let seqs: Vec<&[u8]> = vec![
+///     b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGTAGCTAGC",
+///     b"GGGTTTCCCAAAGGGTTTCCCAAATTTGCGCGCATATATCGCGAT",
+///     b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGTAGCTAGC",
+/// ];
+/// let pairs = similarity_join(&seqs, 11, 0.8);
+/// assert_eq!(pairs, vec![(0, 2, 1.0)]);
+/// ```
+pub fn similarity_join(seqs: &[&[u8]], k: usize, threshold: f64) -> Vec<(usize, usize, f64)> {
+    let signatures: Vec<Vec<u64>> = seqs.iter().map(|seq| signature(seq, k)).collect();
+
+    // Use the most rows per band for which the LSH threshold, (1 / bands)^(1 / rows), does not
+    // exceed the requested threshold.
+    let mut rows = 1;
+    while rows < SIGNATURE_LEN {
+        let next = rows * 2;
+        let bands = SIGNATURE_LEN / next;
+        if (1.0 / bands as f64).powf(1.0 / next as f64) > threshold {
+            break;
+        }
+        rows = next;
+    }
+
+    let mut candidates = Vec::new();
+    for band in 0..SIGNATURE_LEN / rows {
+        let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+        for (i, signature) in signatures.iter().enumerate() {
+            buckets
+                .entry(&signature[band * rows..(band + 1) * rows])
+                .or_default()
+                .push(i);
+        }
+        for bucket in buckets.values() {
+            for (x, &i) in bucket.iter().enumerate() {
+                for &j in &bucket[x + 1..] {
+                    candidates.push((i, j));
+                }
+            }
+        }
+    }
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    candidates
+        .into_iter()
+        .filter_map(|(i, j)| {
+            let similarity = signature_similarity(&signatures[i], &signatures[j]);
+            if similarity >= threshold {
+                Some((i, j, similarity))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabets::dna;
+    use crate::data_structures::test_utils::random_seq;
+
+    #[test]
+    fn test_tanimoto() {
+        let seq = b"ATGCGTACGTTAGCATCGGATCGATCGTAGCTAGCATGCATCGATCGGCTAGCGCATTA";
+        let other = b"TTGACCATGCAGGTACCAGTACGATCAGCATGGGTTTCCCAAAGGGCATCGA";
+        let k = 7;
+
+        assert_eq!(
+            tanimoto(&fingerprint(seq, k, 1024), &fingerprint(seq, k, 1024)),
+            1.0
+        );
+
+        let mut previous = 0.0;
+        for &bits in &[4096, 512, 64, 8, 1] {
+            let similarity = tanimoto(&fingerprint(seq, k, bits), &fingerprint(other, k, bits));
+            assert!(similarity >= previous && similarity <= 1.0);
+            previous = similarity;
+        }
+        assert!(tanimoto(&fingerprint(seq, k, 4096), &fingerprint(other, k, 4096)) < 0.1);
+        assert_eq!(previous, 1.0);
+    }
+
+    #[test]
+    fn test_hashed_kmer_features() {
+        let seq = random_seq(300, 7);
+        let k = 9;
+        let features = hashed_kmer_features(&seq, k, 64);
+        assert_eq!(features.len(), 64);
+        assert_eq!(features.iter().sum::<f32>(), (seq.len() - k + 1) as f32);
+        assert_eq!(features, hashed_kmer_features(&seq, k, 64));
+
+        let signed = signed_hashed_kmer_features(&seq, k, 64);
+        assert_eq!(signed, signed_hashed_kmer_features(&seq, k, 64));
+        assert!(signed.iter().any(|&x| x < 0.0));
+        for (x, y) in signed.iter().zip(&features) {
+            assert!(x.abs() <= *y);
+        }
+
+        assert_eq!(hashed_kmer_features(b"ACG", 4, 8), vec![0.0; 8]);
+    }
+
+    #[test]
+    fn test_estimate_identity() {
+        let read = random_seq(5000, 3);
+        assert_eq!(estimate_identity(&read, &read, 15), 1.0);
+        assert_eq!(estimate_identity(&read, &dna::revcomp(&read), 15), 1.0);
+
+        // substitute every 50th base, i.e. 98% identity
+        let mut mutated = read.clone();
+        for base in mutated.iter_mut().step_by(50) {
+            *base = if *base == b'A' { b'C' } else { b'A' };
+        }
+        let identity = estimate_identity(&read, &mutated, 15);
+        assert!((identity - 0.98).abs() < 0.005);
+
+        assert!(estimate_identity(&read, &random_seq(5000, 4), 15) < 0.8);
+        assert_eq!(estimate_identity(b"ACG", b"ACG", 15), 0.0);
+    }
+
+    #[test]
+    fn test_folded_kmer_set() {
+        let k = 21;
+        let seq = random_seq(1020, 5);
+        let others = random_seq(10020, 6);
+        let inserted: Vec<Kmer> = seq.windows(k).map(|w| Kmer::new(k, w)).collect();
+        let n = inserted.len() as f64;
+
+        let mut previous = 1.0;
+        for &bits in &[10, 12, 14, 16] {
+            let mut set = FoldedKmerSet::new(bits);
+            for kmer in &inserted {
+                set.insert(kmer);
+            }
+            assert!(inserted.iter().all(|kmer| set.contains(kmer)));
+
+            let false_positives = others
+                .windows(k)
+                .filter(|w| set.contains(&Kmer::new(k, w)))
+                .count();
+            let rate = false_positives as f64 / (others.len() - k + 1) as f64;
+            let expected = 1.0 - (-n / f64::from(1u32 << bits)).exp();
+            assert!((rate - expected).abs() < 0.25 * expected + 0.005);
+            assert!(rate < previous);
+            previous = rate;
+        }
+    }
+
+    #[test]
+    fn test_minhash_jaccard() {
+        let k = 21;
+        let genome = random_seq(5000, 13);
+        let sketch = MinHashSketch::from_sequence(&genome, k, 500);
+        assert_eq!(sketch.jaccard(&sketch), 1.0);
+
+        // both strands sketch identically
+        let revcomp = MinHashSketch::from_sequence(&dna::revcomp(&genome), k, 500);
+        assert_eq!(revcomp, sketch);
+        assert_eq!(revcomp.jaccard(&sketch), 1.0);
+
+        let disjoint = MinHashSketch::from_sequence(&random_seq(5000, 14), k, 500);
+        assert!(disjoint.jaccard(&sketch) < 0.01);
+
+        // two halves overlapping by a third of their k-mers
+        let a = MinHashSketch::from_sequence(&genome[..3000], k, 500);
+        let b = MinHashSketch::from_sequence(&genome[2000..], k, 500);
+        let expected = (1000 - k + 1) as f64 / (5000 - k + 1) as f64;
+        assert!((a.jaccard(&b) - expected).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_minhash_containment() {
+        let k = 21;
+        let genome = random_seq(5000, 8);
+        let sketch = MinHashSketch::from_sequence(&genome, k, 1000);
+        assert_eq!(sketch.len(), 1000);
+
+        let read = MinHashSketch::from_sequence(&genome[1000..1300], k, usize::MAX);
+        assert_eq!(read.containment(&sketch), 1.0);
+        let other = MinHashSketch::from_sequence(&random_seq(300, 9), k, usize::MAX);
+        assert_eq!(other.containment(&sketch), 0.0);
+
+        // half of the read comes from the genome
+        let chimera = [&genome[2000..2300], &random_seq(300, 10)[..]].concat();
+        let containment =
+            MinHashSketch::from_sequence(&chimera, k, usize::MAX).containment(&sketch);
+        assert!(containment > 0.3 && containment < 0.7);
+    }
+
+    #[test]
+    fn test_classify() {
+        let k = 21;
+        let genomes = [random_seq(5000, 11), random_seq(5000, 12)];
+        let references = vec![
+            ("first", MinHashSketch::from_sequence(&genomes[0], k, 1000)),
+            ("second", MinHashSketch::from_sequence(&genomes[1], k, 1000)),
+        ];
+        assert_eq!(
+            classify(&genomes[0][3000..3250], &references, k, 0.8),
+            Some("first")
+        );
+        assert_eq!(
+            classify(
+                &dna::revcomp(&genomes[1][100..350]),
+                &references,
+                k,
+                0.8
+            ),
+            Some("second")
+        );
+        assert_eq!(classify(&random_seq(250, 13), &references, k, 0.8), None);
+    }
+
+    #[test]
+    fn test_similarity_join() {
+        let base = random_seq(500, 1);
+        let mut near_duplicate = base.clone();
+        near_duplicate[250] = if base[250] == b'A' { b'C' } else { b'A' };
+        let others: Vec<Vec<u8>> = (2..6).map(|seed| random_seq(500, seed)).collect();
+
+        let mut seqs: Vec<&[u8]> = others.iter().map(|seq| seq.as_slice()).collect();
+        seqs.insert(1, &base);
+        seqs.push(&near_duplicate);
+
+        let pairs = similarity_join(&seqs, 15, 0.8);
+        assert_eq!(pairs.len(), 1);
+        let (i, j, similarity) = pairs[0];
+        assert_eq!((i, j), (1, seqs.len() - 1));
+        assert!(similarity > 0.8);
+
+        assert!(similarity_join(&[], 15, 0.8).is_empty());
+    }
+}
@@ -0,0 +1,758 @@
+//! Minimizer-based indexing of DNA sequences.
+//!
+//! For every window of `w` consecutive k-mers, the k-mer with the smallest hash is selected as the
+//! window's minimizer. Minimizers of adjacent windows usually coincide, so a sequence is
+//! represented by a small subsample of its k-mers that is nevertheless shared between overlapping
+//! sequences.
+//!
+//! Selecting minimizers this way is known as winnowing, see `winnow`.
+//! A `MinimizerIndex` maps each minimizer to the sequences and positions where it was selected.
+//! It can be written to disk with `MinimizerIndex::save` and mapped back into memory with
+//! `MinimizerIndex::load_mmap`, which allows to query a prebuilt reference index without
+//! rebuilding or deserializing it.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::data_structures::minimizer::MinimizerIndex;
+//!
+//! let seqs: Vec<&[u8]> = vec![b"ACGTTGCATGTCGCATGATGCATGAGAGCT", b"GGGTTTCCCAAAGGGTTTCCCAAA"];
+//! let index = MinimizerIndex::from_sequences(&seqs, 5, 4);
+//!
+//! // every hit of a substring of the first sequence points into the first sequence
+//! let hits = index.query(b"GTCGCATGATGC");
+//! assert!(!hits.is_empty());
+//! assert!(hits.iter().all(|&(_, seq_id, _)| seq_id == 0));
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use bio_types::strand::Strand;
+use memmap::Mmap;
+
+use crate::alphabets::dna;
+use crate::data_structures::kmer::{hash_kmer, Kmer};
+
+const MAGIC: &[u8; 8] = b"BIOMMIDX";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 40;
+
+/// Winnow `seq`: select the `(hash, position)` pairs of its minimizers, i.e. of the k-mers with
+/// the smallest hash in each window of `w` consecutive k-mers. Ties are broken in favour of the
+/// leftmost k-mer and a k-mer selected by several consecutive windows is reported once.
+/// Sequences with fewer than `w` k-mers are treated as a single window.
+///
+/// Any two sequences sharing a substring of length at least `w + k - 1` share at least one
+/// minimizer.
+///
+/// # Panics
+///
+/// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::minimizer::winnow;
+///
+/// let minimizers = winnow(b"ACGTTGCATGTCGCATGATGCATGAG", 5, 4);
+/// assert!(minimizers.windows(2).all(|m| m[0].1 < m[1].1));
+/// ```
+pub fn winnow(seq: &[u8], k: usize, w: usize) -> Vec<(u64, usize)> {
+    if k == 0 || seq.len() < k {
+        return Vec::new();
+    }
+    let hashes: Vec<u64> = seq
+        .windows(k)
+        .map(|window| hash_kmer(&Kmer::new(k, window)))
+        .collect();
+    window_minima(&hashes, w)
+        .into_iter()
+        .map(|pos| (hashes[pos], pos))
+        .collect()
+}
+
+/// Return the `(position, k-mer)` pairs of the minimizers of `seq`, selected as in `winnow`:
+/// for each window of `w` consecutive k-mers, the k-mer with the smallest hash, the leftmost one
+/// on hash ties. A k-mer selected by several consecutive windows is reported once.
+///
+/// # Panics
+///
+/// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::Kmer;
+/// use bio::data_structures::minimizer::minimizers;
+///
+/// let seq = b"ACGTTGCATGTCGCATGATGCATGAG";
+/// for (pos, kmer) in minimizers(seq, 5, 4) {
+///     assert_eq!(kmer, Kmer::new(5, &seq[pos..pos + 5]));
+/// }
+/// ```
+pub fn minimizers(seq: &[u8], k: usize, w: usize) -> Vec<(usize, Kmer)> {
+    winnow(seq, k, w)
+        .into_iter()
+        .map(|(_, pos)| (pos, Kmer::new(k, &seq[pos..pos + k])))
+        .collect()
+}
+
+/// Select minimizers by the hash of their canonical k-mer, such that a sequence and its reverse
+/// complement yield the same minimizer hashes. Returns `(hash, position, strand)` triples, where
+/// the strand tells whether the k-mer at the position is the canonical k-mer
+/// (`Strand::Forward`) or its reverse complement (`Strand::Reverse`). Palindromic k-mers, which
+/// are their own reverse complement, are reported with `Strand::Unknown`.
+/// Windows and ties are handled as in `winnow`.
+///
+/// # Panics
+///
+/// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+pub fn canonical_minimizers(seq: &[u8], k: usize, w: usize) -> Vec<(u64, usize, Strand)> {
+    if k == 0 || seq.len() < k {
+        return Vec::new();
+    }
+    let (hashes, strands): (Vec<u64>, Vec<Strand>) = seq
+        .windows(k)
+        .map(|window| {
+            let kmer = Kmer::new(k, window);
+            let canonical = kmer.canonical();
+            let strand = if dna::revcomp(window) == window {
+                Strand::Unknown
+            } else if canonical == kmer {
+                Strand::Forward
+            } else {
+                Strand::Reverse
+            };
+            (hash_kmer(&canonical), strand)
+        })
+        .unzip();
+    window_minima(&hashes, w)
+        .into_iter()
+        .map(|pos| (hashes[pos], pos, strands[pos]))
+        .collect()
+}
+
+/// Return the positions of the minima of all windows of `w` consecutive hashes, choosing the
+/// leftmost on ties and reporting a position selected by consecutive windows once.
+fn window_minima(hashes: &[u64], w: usize) -> Vec<usize> {
+    let w = w.max(1).min(hashes.len());
+    let mut minima: Vec<usize> = Vec::new();
+    if hashes.is_empty() {
+        return minima;
+    }
+    for start in 0..=hashes.len() - w {
+        let mut best = start;
+        for pos in start + 1..start + w {
+            if hashes[pos] < hashes[best] {
+                best = pos;
+            }
+        }
+        if minima.last() != Some(&best) {
+            minima.push(best);
+        }
+    }
+    minima
+}
+
+/// An in-memory minimizer index over a collection of sequences.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimizerIndex {
+    k: usize,
+    w: usize,
+    postings: HashMap<u64, Vec<(usize, usize)>>,
+}
+
+impl MinimizerIndex {
+    /// Create an empty index for k-mers of length `k` and windows of `w` consecutive k-mers.
+    pub fn new(k: usize, w: usize) -> Self {
+        assert!(k > 0, "k must be positive");
+        assert!(w > 0, "w must be positive");
+        MinimizerIndex {
+            k,
+            w,
+            postings: HashMap::new(),
+        }
+    }
+
+    /// Build an index over the given sequences. Each sequence is identified by its position in
+    /// `seqs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a sequence contains a character that [`Kmer::new`] rejects.
+    pub fn from_sequences(seqs: &[&[u8]], k: usize, w: usize) -> Self {
+        let mut index = MinimizerIndex::new(k, w);
+        for (seq_id, seq) in seqs.iter().enumerate() {
+            index.insert(seq_id, seq);
+        }
+        index
+    }
+
+    /// Add the minimizers of `seq` to the index, recording them under `seq_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn insert(&mut self, seq_id: usize, seq: &[u8]) {
+        for (hash, pos) in winnow(seq, self.k, self.w) {
+            self.postings.entry(hash).or_default().push((seq_id, pos));
+        }
+    }
+
+    /// Remove the minimizers that occur more than `max_occ` times, such that ubiquitous
+    /// minimizers of repeats do not dominate the hits of `query` (similar to the `-f` option of
+    /// minimap2). Returns the number of removed minimizers. Minimizers of sequences inserted
+    /// afterwards are not filtered.
+    pub fn filter_frequent(&mut self, max_occ: usize) -> usize {
+        let len = self.postings.len();
+        self.postings
+            .retain(|_, postings| postings.len() <= max_occ);
+        len - self.postings.len()
+    }
+
+    /// The k-mer length of the index.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The window size (in k-mers) of the index.
+    pub fn w(&self) -> usize {
+        self.w
+    }
+
+    /// Number of distinct minimizers in the index.
+    pub fn len(&self) -> usize {
+        self.postings.len()
+    }
+
+    /// Whether the index contains no minimizers.
+    pub fn is_empty(&self) -> bool {
+        self.postings.is_empty()
+    }
+
+    /// The `(seq_id, position)` occurrences of the minimizer with the given hash.
+    pub fn get(&self, minimizer: u64) -> Option<&[(usize, usize)]> {
+        self.postings.get(&minimizer).map(|p| p.as_slice())
+    }
+
+    /// Look up all minimizers of `seq` in the index. Returns a list of
+    /// `(query position, seq_id, target position)` hits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn query(&self, seq: &[u8]) -> Vec<(usize, usize, usize)> {
+        let mut hits = Vec::new();
+        for (hash, query_pos) in winnow(seq, self.k, self.w) {
+            if let Some(postings) = self.get(hash) {
+                hits.extend(
+                    postings
+                        .iter()
+                        .map(|&(seq_id, pos)| (query_pos, seq_id, pos)),
+                );
+            }
+        }
+        hits
+    }
+
+    /// Write the index to `path` in a compact binary layout: a header (magic, version, k, w and
+    /// table sizes), the sorted minimizer hashes, the offsets of their posting lists and finally
+    /// the concatenated `(seq_id, position)` posting lists. All values are little-endian.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(File::create(path)?);
+
+        let mut keys: Vec<u64> = self.postings.keys().cloned().collect();
+        keys.sort_unstable();
+        let n_postings: usize = self.postings.values().map(|p| p.len()).sum();
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(self.k as u32).to_le_bytes())?;
+        writer.write_all(&(self.w as u32).to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&(keys.len() as u64).to_le_bytes())?;
+        writer.write_all(&(n_postings as u64).to_le_bytes())?;
+
+        for key in &keys {
+            writer.write_all(&key.to_le_bytes())?;
+        }
+        let mut offset = 0u64;
+        writer.write_all(&offset.to_le_bytes())?;
+        for key in &keys {
+            offset += self.postings[key].len() as u64;
+            writer.write_all(&offset.to_le_bytes())?;
+        }
+        for key in &keys {
+            for &(seq_id, pos) in &self.postings[key] {
+                writer.write_all(&(seq_id as u64).to_le_bytes())?;
+                writer.write_all(&(pos as u64).to_le_bytes())?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Memory-map an index previously written with `MinimizerIndex::save`. Queries read directly
+    /// from the mapped file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `InvalidData` if the header does not describe a valid index or
+    /// the file size does not match the header.
+    pub fn load_mmap<P: AsRef<Path>>(path: P) -> io::Result<MappedMinimizerIndex> {
+        let file = File::open(path)?;
+        // an empty file cannot be mapped, so check the length before mapping
+        if file.metadata()?.len() < HEADER_LEN as u64 {
+            return Err(invalid_data("Not a minimizer index file."));
+        }
+        // The mapping is only ever read. As with any memory-mapped file, modifying the file while
+        // it is mapped is undefined behaviour.
+        let mmap = unsafe { Mmap::map(&file)? };
+        MappedMinimizerIndex::new(mmap)
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// A minimizer index backed by a memory-mapped file, see `MinimizerIndex::load_mmap`.
+#[derive(Debug)]
+pub struct MappedMinimizerIndex {
+    mmap: Mmap,
+    k: usize,
+    w: usize,
+    n_keys: usize,
+    n_postings: usize,
+}
+
+impl MappedMinimizerIndex {
+    fn new(mmap: Mmap) -> io::Result<Self> {
+        if mmap.len() < HEADER_LEN || &mmap[..8] != MAGIC {
+            return Err(invalid_data("Not a minimizer index file."));
+        }
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(mmap[offset..offset + 4].try_into().unwrap());
+        let read_u64 =
+            |offset: usize| u64::from_le_bytes(mmap[offset..offset + 8].try_into().unwrap());
+
+        if read_u32(8) != VERSION {
+            return Err(invalid_data("Unsupported minimizer index version."));
+        }
+        let k = read_u32(12) as usize;
+        let w = read_u32(16) as usize;
+        if k == 0 || w == 0 {
+            return Err(invalid_data("Invalid k or w in minimizer index header."));
+        }
+        let n_keys = read_u64(24) as usize;
+        let n_postings = read_u64(32) as usize;
+        let expected_len = n_keys
+            .checked_mul(16)
+            .and_then(|len| len.checked_add(8))
+            .and_then(|len| n_postings.checked_mul(16).and_then(|p| len.checked_add(p)))
+            .and_then(|len| len.checked_add(HEADER_LEN));
+        if expected_len != Some(mmap.len()) {
+            return Err(invalid_data(
+                "Minimizer index file size does not match its header.",
+            ));
+        }
+
+        Ok(MappedMinimizerIndex {
+            mmap,
+            k,
+            w,
+            n_keys,
+            n_postings,
+        })
+    }
+
+    fn read_u64(&self, offset: usize) -> u64 {
+        u64::from_le_bytes(self.mmap[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn key(&self, i: usize) -> u64 {
+        self.read_u64(HEADER_LEN + 8 * i)
+    }
+
+    fn offset(&self, i: usize) -> usize {
+        self.read_u64(HEADER_LEN + 8 * self.n_keys + 8 * i) as usize
+    }
+
+    /// The bytes of the postings `start..end` within the mapped file.
+    fn postings(&self, start: usize, end: usize) -> &[u8] {
+        let first = HEADER_LEN + 16 * self.n_keys + 8;
+        &self.mmap[first + 16 * start..first + 16 * end]
+    }
+
+    /// The k-mer length of the index.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The window size (in k-mers) of the index.
+    pub fn w(&self) -> usize {
+        self.w
+    }
+
+    /// Number of distinct minimizers in the index.
+    pub fn len(&self) -> usize {
+        self.n_keys
+    }
+
+    /// Whether the index contains no minimizers.
+    pub fn is_empty(&self) -> bool {
+        self.n_keys == 0
+    }
+
+    /// The `(seq_id, position)` occurrences of the minimizer with the given hash, decoded
+    /// lazily from the mapped file.
+    pub fn get(&self, minimizer: u64) -> Option<MappedPostings<'_>> {
+        // binary search over the sorted minimizer table
+        let (mut lo, mut hi) = (0, self.n_keys);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let key = self.key(mid);
+            if key < minimizer {
+                lo = mid + 1;
+            } else if key > minimizer {
+                hi = mid;
+            } else {
+                let (start, end) = (self.offset(mid), self.offset(mid + 1));
+                if start > end || end > self.n_postings {
+                    return None;
+                }
+                return Some(MappedPostings {
+                    chunks: self.postings(start, end).chunks_exact(16),
+                });
+            }
+        }
+        None
+    }
+
+    /// Look up all minimizers of `seq` in the index. Returns a list of
+    /// `(query position, seq_id, target position)` hits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn query(&self, seq: &[u8]) -> Vec<(usize, usize, usize)> {
+        let mut hits = Vec::new();
+        for (hash, query_pos) in winnow(seq, self.k, self.w) {
+            if let Some(postings) = self.get(hash) {
+                hits.extend(postings.map(|(seq_id, pos)| (query_pos, seq_id, pos)));
+            }
+        }
+        hits
+    }
+}
+
+/// An iterator over the `(seq_id, position)` occurrences of a minimizer in a
+/// `MappedMinimizerIndex`, reading them directly from the mapped file.
+#[derive(Clone, Debug)]
+pub struct MappedPostings<'a> {
+    chunks: std::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for MappedPostings<'a> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        self.chunks.next().map(|posting| {
+            let read_u64 = |bytes: &[u8]| u64::from_le_bytes(bytes.try_into().unwrap()) as usize;
+            (read_u64(&posting[..8]), read_u64(&posting[8..]))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for MappedPostings<'a> {}
+
+/// A sketch of a sequence consisting of the hashes of its canonical minimizers (see
+/// `canonical_minimizers`), such that a sequence and its reverse complement have the same
+/// sketch. Since the minimizers are a deterministic subsample of the k-mers, a sketch is cheaper
+/// to build than a MinHash signature, while the Jaccard similarity of two sketches still
+/// approximates that of the k-mer sets.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::minimizer::MinimizerSketch;
+///
+/// let a = MinimizerSketch::from_sequence(b"ACGTTGCATGTCGCATGATGCATGAGAGCT", 7, 5);
+/// let b = MinimizerSketch::from_sequence(b"AGCTCTCATGCATCATGCGACATGCAACGT", 7, 5);
+/// // b is the reverse complement of a
+/// assert_eq!(a.distance(&b), 0.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimizerSketch {
+    k: usize,
+    w: usize,
+    hashes: HashSet<u64>,
+}
+
+impl MinimizerSketch {
+    /// Sketch `seq` by its canonical minimizers for k-mers of length `k` and windows of `w`
+    /// consecutive k-mers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seq` contains a character that [`Kmer::new`] rejects.
+    pub fn from_sequence(seq: &[u8], k: usize, w: usize) -> Self {
+        MinimizerSketch {
+            k,
+            w,
+            hashes: canonical_minimizers(seq, k, w)
+                .into_iter()
+                .map(|(hash, _, _)| hash)
+                .collect(),
+        }
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn w(&self) -> usize {
+        self.w
+    }
+
+    /// The number of distinct minimizers in the sketch.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Jaccard similarity of the minimizer sets of both sketches. Two empty sketches have a
+    /// similarity of 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sketches were built with different `k` or `w`.
+    pub fn jaccard(&self, other: &MinimizerSketch) -> f64 {
+        assert!(
+            self.k == other.k && self.w == other.w,
+            "sketches must use the same k and w"
+        );
+        let union = self.hashes.union(&other.hashes).count();
+        if union == 0 {
+            return 1.0;
+        }
+        self.hashes.intersection(&other.hashes).count() as f64 / union as f64
+    }
+
+    /// Jaccard distance between both sketches, i.e. `1 - jaccard`, ranging from 0 for
+    /// identical minimizer sets to 1 for disjoint ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sketches were built with different `k` or `w`.
+    pub fn distance(&self, other: &MinimizerSketch) -> f64 {
+        1.0 - self.jaccard(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structures::test_utils::random_seq;
+    use std::collections::HashSet;
+
+    fn example_index() -> MinimizerIndex {
+        let seqs: Vec<&[u8]> = vec![
+            b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGTAGCTAGCTAGC",
+            b"GGGTTTCCCAAAGGGTTTCCCAAATTTGCGCGCATATATCGCGAT",
+            b"TTGACCATGCAGGTACCAGTACGATCAGCAT",
+        ];
+        MinimizerIndex::from_sequences(&seqs, 7, 5)
+    }
+
+    #[test]
+    fn test_query_finds_source_sequence() {
+        let index = example_index();
+        let hits = index.query(b"GCATGTCGCATGATGCATGAG");
+        assert!(!hits.is_empty());
+        assert!(hits.iter().all(|&(_, seq_id, _)| seq_id == 0));
+    }
+
+    #[test]
+    fn test_save_load_mmap_roundtrip() {
+        let index = example_index();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.bin");
+        index.save(&path).unwrap();
+
+        let mapped = MinimizerIndex::load_mmap(&path).unwrap();
+        assert_eq!(mapped.k(), index.k());
+        assert_eq!(mapped.w(), index.w());
+        assert_eq!(mapped.len(), index.len());
+        for (&key, postings) in &index.postings {
+            let mapped_postings = mapped.get(key).unwrap();
+            assert_eq!(mapped_postings.len(), postings.len());
+            assert_eq!(mapped_postings.collect::<Vec<_>>(), *postings);
+        }
+        assert!(mapped.get(0).is_none() && index.get(0).is_none());
+
+        for query in &[
+            &b"GCATGTCGCATGATGCATGAG"[..],
+            b"CCCAAAGGGTTTCCCAAATTTGC",
+            b"ACCAGTACGATCAG",
+            b"AAAAAAAAAAAAAAAA",
+        ] {
+            assert_eq!(mapped.query(query), index.query(query));
+        }
+    }
+
+    #[test]
+    fn test_load_mmap_invalid_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.bin");
+        std::fs::write(&path, b"not an index at all, but long enough for a header").unwrap();
+        let err = MinimizerIndex::load_mmap(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        std::fs::write(&path, b"").unwrap();
+        let err = MinimizerIndex::load_mmap(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let index = example_index();
+        index.save(&path).unwrap();
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes.truncate(bytes.len() - 1);
+        std::fs::write(&path, &bytes).unwrap();
+        let err = MinimizerIndex::load_mmap(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_winnow_shared_substring() {
+        let (k, w) = (5, 4);
+        let seq = b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGTAGCTAGCTAGC";
+        let minimizers = winnow(seq, k, w);
+        for start in 0..seq.len() - (w + k - 1) {
+            let query = winnow(&seq[start..start + w + k - 1], k, w);
+            assert!(query
+                .iter()
+                .all(|&(hash, pos)| minimizers.contains(&(hash, start + pos))));
+        }
+    }
+
+    #[test]
+    fn test_minimizers() {
+        // hashes of the 3-mers of GATTACAT ordered: TAC < CAT < GAT < ATT < TTA < ACA
+        let kmer = |seq| Kmer::from_literal(seq);
+        assert_eq!(
+            minimizers(b"GATTACAT", 3, 3),
+            vec![(0, kmer("GAT")), (3, kmer("TAC"))]
+        );
+        assert_eq!(
+            minimizers(b"GATTACAT", 3, 2),
+            vec![
+                (0, kmer("GAT")),
+                (1, kmer("ATT")),
+                (3, kmer("TAC")),
+                (5, kmer("CAT"))
+            ]
+        );
+        // all hashes tie, so the leftmost k-mer of each window is selected
+        assert_eq!(minimizers(b"AAAAA", 3, 3), vec![(0, kmer("AAA"))]);
+        assert_eq!(
+            minimizers(b"AAAAA", 3, 2),
+            vec![(0, kmer("AAA")), (1, kmer("AAA"))]
+        );
+        assert!(minimizers(b"GA", 3, 2).is_empty());
+    }
+
+    #[test]
+    fn test_filter_frequent() {
+        let repeat: &[u8] = b"GATTACAGATCC";
+        let mut seqs: Vec<Vec<u8>> = vec![b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCG".to_vec()];
+        for flank in &[&b"TTGACCATGCAGG"[..], b"GGGTTTCCCAAAG", b"CATATATCGCGAT"] {
+            seqs.push([*flank, repeat, *flank].concat());
+        }
+        let seqs: Vec<&[u8]> = seqs.iter().map(|seq| seq.as_slice()).collect();
+        let (k, w) = (7, 3);
+        let mut index = MinimizerIndex::from_sequences(&seqs, k, w);
+
+        let repeat_minimizers: Vec<u64> = winnow(repeat, k, w)
+            .into_iter()
+            .map(|(hash, _)| hash)
+            .filter(|&hash| index.get(hash).unwrap().len() > 2)
+            .collect();
+        assert!(!repeat_minimizers.is_empty());
+        let unique_hits = index.query(&seqs[0][5..25]);
+        assert!(!unique_hits.is_empty());
+
+        let len = index.len();
+        let removed = index.filter_frequent(2);
+        assert!(removed >= repeat_minimizers.len());
+        assert_eq!(index.len(), len - removed);
+        for hash in repeat_minimizers {
+            assert!(index.get(hash).is_none());
+        }
+        assert!(index.query(repeat).is_empty());
+        assert_eq!(index.query(&seqs[0][5..25]), unique_hits);
+    }
+
+    #[test]
+    fn test_canonical_minimizers() {
+        let (k, w) = (7, 5);
+        let seq = b"ACGTTGCATGTCGCATGATGCATGAGAGCTACGATCGATCGTAGCTAGCTAGC";
+        let revcomp = dna::revcomp(&seq[..]);
+
+        let forward = canonical_minimizers(seq, k, w);
+        let reverse = canonical_minimizers(&revcomp, k, w);
+        let hashes = |minimizers: &[(u64, usize, Strand)]| {
+            minimizers
+                .iter()
+                .map(|&(hash, _, _)| hash)
+                .collect::<HashSet<u64>>()
+        };
+        assert_eq!(hashes(&forward), hashes(&reverse));
+
+        for &(_, pos, strand) in &forward {
+            let kmer = Kmer::new(k, &seq[pos..pos + k]);
+            match strand {
+                Strand::Forward => assert_eq!(kmer.canonical(), kmer),
+                Strand::Reverse => assert_ne!(kmer.canonical(), kmer),
+                Strand::Unknown => assert_eq!(
+                    dna::revcomp(kmer.decode().as_bytes()),
+                    kmer.decode().as_bytes()
+                ),
+            }
+        }
+        let palindrome = canonical_minimizers(b"ACGT", 4, 1);
+        assert_eq!(palindrome.len(), 1);
+        assert_eq!(palindrome[0].0, hash_kmer(&Kmer::from_literal("ACGT")));
+        assert!(matches!(palindrome[0].2, Strand::Unknown));
+    }
+
+    #[test]
+    fn test_minimizer_sketch() {
+        let (k, w) = (15, 10);
+        let seq = random_seq(2000, 1);
+        let sketch = MinimizerSketch::from_sequence(&seq, k, w);
+        assert!(!sketch.is_empty());
+        assert_eq!(sketch.distance(&sketch), 0.0);
+        assert_eq!(
+            sketch.distance(&MinimizerSketch::from_sequence(&dna::revcomp(&seq), k, w)),
+            0.0
+        );
+
+        let unrelated = MinimizerSketch::from_sequence(&random_seq(2000, 2), k, w);
+        assert!(sketch.distance(&unrelated) > 0.95);
+
+        let half = MinimizerSketch::from_sequence(&seq[..1000], k, w);
+        let distance = sketch.distance(&half);
+        assert!(distance > 0.3 && distance < 0.7);
+    }
+}
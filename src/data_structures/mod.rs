@@ -7,6 +7,7 @@
 
 pub mod annot_map;
 pub mod bit_tree;
+pub mod bloom;
 pub mod bitenc;
 pub mod bwt;
 pub mod fmindex;
@@ -17,3 +18,7 @@ pub mod rank_select;
 pub mod smallints;
 pub mod suffix_array;
 pub mod kmer;
+pub mod minimizer;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
@@ -0,0 +1,15 @@
+//! Fixtures shared by the tests of the data structures.
+
+/// Generate a pseudo-random DNA sequence of length `len` with a linear congruential generator,
+/// so that tests are reproducible for a given `seed`.
+pub(crate) fn random_seq(len: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            b"ACGT"[(state >> 62) as usize]
+        })
+        .collect()
+}
@@ -106,12 +106,17 @@ use std::io;
 use std::io::prelude::*;
 use std::path::Path;
 
+use crate::seq_analysis::complexity;
 use crate::utils::{Text, TextSlice};
 use std::fmt;
 
 /// Maximum size of temporary buffer used for reading indexed FASTA files.
 const MAX_FASTA_BUFFER_SIZE: usize = 512;
 
+/// Window size and k-mer length used for low-complexity masking by `Reader`.
+const LOW_COMPLEXITY_WINDOW: usize = 32;
+const LOW_COMPLEXITY_K: usize = 3;
+
 /// Trait for FASTA readers.
 pub trait FastaRead {
     fn read(&mut self, record: &mut Record) -> io::Result<()>;
@@ -122,6 +127,7 @@ pub trait FastaRead {
 pub struct Reader<R: io::Read> {
     reader: io::BufReader<R>,
     line: String,
+    mask_low_complexity: Option<f64>,
 }
 
 impl Reader<fs::File> {
@@ -149,9 +155,32 @@ impl<R: io::Read> Reader<R> {
         Reader {
             reader: io::BufReader::new(reader),
             line: String::new(),
+            mask_low_complexity: None,
         }
     }
 
+    /// Soft-mask low-complexity regions of the returned sequences: if a threshold is given,
+    /// each window of 32 bases whose normalized trinucleotide entropy is below it is
+    /// lowercased (see `seq_analysis::complexity::mask_low_complexity`). Thresholds around 0.5
+    /// mask tandem repeats and homopolymer runs while leaving ordinary sequence untouched.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use bio::io::fasta::Reader;
+    /// const fasta_file: &'static [u8] = b">id
+    /// ATATATATATATATATATATATATATATATATATAT
+    /// ";
+    /// let mut records = Reader::new(fasta_file)
+    ///     .mask_low_complexity(Some(0.5))
+    ///     .records();
+    /// let record = records.next().unwrap().unwrap();
+    /// assert_eq!(record.seq().to_vec(), b"atatatatatatatatatatatatatatatatatat");
+    /// ```
+    pub fn mask_low_complexity(mut self, threshold: Option<f64>) -> Self {
+        self.mask_low_complexity = threshold;
+        self
+    }
+
     /// Return an iterator over the records of this Fasta file.
     ///
     /// # Example
@@ -246,6 +275,17 @@ where
             }
             record.seq.push_str(self.line.trim_end());
         }
+        if let Some(threshold) = self.mask_low_complexity {
+            let mut seq = std::mem::take(&mut record.seq).into_bytes();
+            complexity::mask_low_complexity(
+                &mut seq,
+                LOW_COMPLEXITY_WINDOW,
+                LOW_COMPLEXITY_K,
+                threshold,
+            );
+            // lowercasing ASCII characters keeps the sequence valid UTF-8
+            record.seq = String::from_utf8(seq).unwrap();
+        }
 
         Ok(())
     }
@@ -1011,6 +1051,47 @@ ATTGTTGTTTTA
         );
     }
 
+    #[test]
+    fn test_reader_mask_low_complexity() {
+        let complex = "ATGCGTACGTTAGCATCGGATCGATCGTAGCTAGCATGCATCGATCGGCTAGCGCATTA";
+        let repeat = "CAG".repeat(15);
+        let fasta = format!(
+            ">id\n{}\n{}{}\n>id2\n{}\n",
+            complex, repeat, complex, complex
+        );
+
+        let mut records = Reader::new(fasta.as_bytes())
+            .mask_low_complexity(Some(0.5))
+            .records();
+        let record = records.next().unwrap().unwrap();
+        let seq = record.seq();
+        let start = complex.len();
+        let end = start + repeat.len();
+        assert!(seq[start..end].iter().all(u8::is_ascii_lowercase));
+        assert!(seq[..start - 10].iter().all(u8::is_ascii_uppercase));
+        assert!(seq[end + 10..].iter().all(u8::is_ascii_uppercase));
+        assert!(seq.eq_ignore_ascii_case(format!("{}{}{}", complex, repeat, complex).as_bytes()));
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.seq(), complex.as_bytes());
+
+        // masking is disabled by default
+        let record = Reader::new(fasta.as_bytes())
+            .records()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(record.seq().iter().all(u8::is_ascii_uppercase));
+
+        // a record too short to hold two trinucleotides is left unmasked
+        let record = Reader::new(&b">id\nACG\n"[..])
+            .mask_low_complexity(Some(0.5))
+            .records()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.seq(), b"ACG");
+    }
+
     #[test]
     fn test_reader_non_ascii_sequence() {
         let mut reader = Reader::new(&b">id\nACGTA\xE2\x98\xB9AT\n"[..]);
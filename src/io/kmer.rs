@@ -0,0 +1,370 @@
+//! Reading of FASTA and FASTQ files directly into 2-bit encoded `Kmer`s, and writing of `Kmer`s
+//! back to FASTA.
+//!
+//! Each record is encoded as a single `Kmer` spanning the whole sequence, which can then be
+//! broken up with `Kmerizer` or counted with `KmerCounter`.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::data_structures::kmer::Kmer;
+//! use bio::io::kmer;
+//!
+//! let fasta: &[u8] = b">chr1\nGATT\nACA\n>chr2\nCCGG\n";
+//! let records = kmer::read_fasta(fasta)
+//!     .collect::<std::io::Result<Vec<_>>>()
+//!     .unwrap();
+//! assert_eq!(records[0], ("chr1".to_owned(), Kmer::from_literal("GATTACA")));
+//! assert_eq!(records[1], ("chr2".to_owned(), Kmer::from_literal("CCGG")));
+//! ```
+
+use std::io;
+use std::io::{BufRead, Write};
+
+use crate::data_structures::kmer::Kmer;
+use crate::io::fasta;
+
+/// Encode `seq`, reporting invalid bases as `io::ErrorKind::InvalidData` for record `id`.
+fn encode_record(id: &str, seq: &[u8]) -> io::Result<Kmer> {
+    Kmer::try_new(seq.len(), seq).map_err(|e| invalid_data(format!("record '{}': {}", id, e)))
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Iterate over the records of a FASTA file as pairs of id and encoded sequence. Sequences may
+/// be wrapped over several lines.
+///
+/// # Errors
+///
+/// Each item is an error if the record could not be read, or if its sequence contains a
+/// character that [`Kmer::new`] rejects. A malformed file ends the iteration after the first
+/// error, while a record with invalid bases does not affect the following records.
+pub fn read_fasta<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<(String, Kmer)>> {
+    fasta::Reader::new(reader).records().map(|record| {
+        let record = record?;
+        let kmer = encode_record(record.id(), record.seq())?;
+        Ok((record.id().to_owned(), kmer))
+    })
+}
+
+/// Number of bases per line written by `write_fasta`.
+pub const FASTA_LINE_WIDTH: usize = 60;
+
+/// Write `records` of id and sequence as FASTA, wrapping sequences at `FASTA_LINE_WIDTH` bases
+/// per line.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::Kmer;
+/// use bio::io::kmer;
+///
+/// let kmer = Kmer::from_literal("GATTACA");
+/// let mut fasta = Vec::new();
+/// kmer::write_fasta(&mut fasta, vec![("chr1", &kmer)]).unwrap();
+/// assert_eq!(fasta, b">chr1\nGATTACA\n");
+/// ```
+pub fn write_fasta<'a, W: Write>(
+    writer: W,
+    records: impl IntoIterator<Item = (&'a str, &'a Kmer)>,
+) -> io::Result<()> {
+    write_fasta_wrapped(writer, records, FASTA_LINE_WIDTH)
+}
+
+/// Write `records` of id and sequence as FASTA, wrapping sequences at `line_width` bases per
+/// line.
+///
+/// # Panics
+///
+/// Panics if `line_width` is 0.
+pub fn write_fasta_wrapped<'a, W: Write>(
+    mut writer: W,
+    records: impl IntoIterator<Item = (&'a str, &'a Kmer)>,
+    line_width: usize,
+) -> io::Result<()> {
+    assert!(line_width > 0, "line width must be positive");
+    for (id, kmer) in records {
+        writeln!(writer, ">{}", id)?;
+        for line in kmer.decode().as_bytes().chunks(line_width) {
+            writer.write_all(line)?;
+            writer.write_all(b"\n")?;
+        }
+    }
+    Ok(())
+}
+
+/// A FASTQ record with its sequence encoded as a `Kmer`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FastqRecord {
+    pub id: String,
+    pub sequence: Kmer,
+    /// The raw quality line, one byte per base.
+    pub qual: Vec<u8>,
+}
+
+/// Iterate over the records of a FASTQ file. Each record has to consist of four lines: the
+/// `@id` header, the sequence, the `+` separator and the qualities.
+///
+/// # Errors
+///
+/// Each item is an error if the record is incomplete or malformed (missing `@` or `+`,
+/// sequence and qualities of different length), which ends the iteration, or if its sequence
+/// contains a character that [`Kmer::new`] rejects, after which the following records are
+/// still read.
+///
+/// # Example
+///
+/// ```
+/// use bio::data_structures::kmer::Kmer;
+/// use bio::io::kmer;
+///
+/// let fastq: &[u8] = b"@read1\nGATTACA\n+\nIIIIII#\n";
+/// let record = kmer::read_fastq(fastq).next().unwrap().unwrap();
+/// assert_eq!(record.id, "read1");
+/// assert_eq!(record.sequence, Kmer::from_literal("GATTACA"));
+/// assert_eq!(record.qual, b"IIIIII#");
+/// ```
+pub fn read_fastq<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<FastqRecord>> {
+    FastqRecords {
+        reader,
+        line: String::new(),
+        error_has_occured: false,
+    }
+}
+
+struct FastqRecords<R: BufRead> {
+    reader: R,
+    line: String,
+    error_has_occured: bool,
+}
+
+impl<R: BufRead> FastqRecords<R> {
+    /// Read the next line without its line terminator, failing at the end of the file.
+    fn read_line(&mut self, id: &str, what: &str) -> io::Result<()> {
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Err(invalid_data(format!(
+                "record '{}': unexpected end of file, expected {}",
+                id, what
+            )));
+        }
+        let len = self.line.trim_end_matches(&['\r', '\n'][..]).len();
+        self.line.truncate(len);
+        Ok(())
+    }
+
+    /// Read the remaining three lines of the record `id`, returning the sequence and the
+    /// qualities.
+    fn read_body(&mut self, id: &str) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        self.read_line(id, "a sequence")?;
+        let seq = self.line.as_bytes().to_vec();
+        self.read_line(id, "a '+' separator")?;
+        if !self.line.starts_with('+') {
+            return Err(invalid_data(format!(
+                "record '{}': expected a '+' separator, found '{}'",
+                id, self.line
+            )));
+        }
+        self.read_line(id, "qualities")?;
+        let qual = self.line.as_bytes().to_vec();
+        if seq.len() != qual.len() {
+            return Err(invalid_data(format!(
+                "record '{}': sequence of length {} but {} qualities",
+                id,
+                seq.len(),
+                qual.len()
+            )));
+        }
+        Ok((seq, qual))
+    }
+
+    /// Read the next record. The outer error is a malformed record, which ends the iteration,
+    /// the inner one a record with invalid bases.
+    fn read_record(&mut self) -> io::Result<Option<io::Result<FastqRecord>>> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            if !self.line.trim().is_empty() {
+                break;
+            }
+        }
+        let id = match self.line.strip_prefix('@') {
+            Some(header) => header
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_owned(),
+            None => {
+                return Err(invalid_data(format!(
+                    "expected '@' at the start of a record, found '{}'",
+                    self.line.trim_end()
+                )))
+            }
+        };
+        let (seq, qual) = self.read_body(&id)?;
+        Ok(Some(encode_record(&id, &seq).map(|sequence| FastqRecord {
+            id,
+            sequence,
+            qual,
+        })))
+    }
+}
+
+impl<R: BufRead> Iterator for FastqRecords<R> {
+    type Item = io::Result<FastqRecord>;
+
+    fn next(&mut self) -> Option<io::Result<FastqRecord>> {
+        if self.error_has_occured {
+            return None;
+        }
+        match self.read_record() {
+            Ok(record) => record,
+            Err(e) => {
+                self.error_has_occured = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FASTA_FILE: &[u8] = b">id desc
+ACCGTAGGCTGA
+CCGTAGGCTGAA
+CCCC
+>id2
+ATTGTTGTTTTA
+GGGG
+";
+
+    #[test]
+    fn test_read_fasta() {
+        let records = read_fasta(FASTA_FILE)
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, "id");
+        assert_eq!(records[0].1.k, 28);
+        assert_eq!(records[0].1.decode(), "ACCGTAGGCTGACCGTAGGCTGAACCCC");
+        assert_eq!(records[1].0, "id2");
+        assert_eq!(records[1].1, Kmer::from_literal("ATTGTTGTTTTAGGGG"));
+    }
+
+    #[test]
+    fn test_write_fasta() {
+        let records = read_fasta(FASTA_FILE)
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        let mut fasta = Vec::new();
+        write_fasta_wrapped(
+            &mut fasta,
+            records.iter().map(|(id, kmer)| (id.as_str(), kmer)),
+            12,
+        )
+        .unwrap();
+        assert_eq!(
+            fasta,
+            b">id\nACCGTAGGCTGA\nCCGTAGGCTGAA\nCCCC\n>id2\nATTGTTGTTTTA\nGGGG\n".to_vec()
+        );
+        let reread = read_fasta(&fasta[..])
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(reread, records);
+
+        let long = Kmer::new(130, &[b'G'; 130]);
+        let mut fasta = Vec::new();
+        write_fasta(&mut fasta, vec![("long", &long)]).unwrap();
+        let lines: Vec<usize> = fasta.split(|&b| b == b'\n').map(<[u8]>::len).collect();
+        assert_eq!(lines, [5, 60, 60, 10, 0]);
+    }
+
+    const FASTQ_FILE: &[u8] = b"@id1 desc
+ACCGTAGGCTGA
++
+IIIIIIIIIIII
+@id2
+ttgu
++id2
+!!#$
+";
+
+    #[test]
+    fn test_read_fastq() {
+        let records = read_fastq(FASTQ_FILE)
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            records,
+            vec![
+                FastqRecord {
+                    id: "id1".to_owned(),
+                    sequence: Kmer::from_literal("ACCGTAGGCTGA"),
+                    qual: b"IIIIIIIIIIII".to_vec(),
+                },
+                FastqRecord {
+                    id: "id2".to_owned(),
+                    sequence: Kmer::from_literal("TTGT"),
+                    qual: b"!!#$".to_vec(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_fastq_errors() {
+        let mut records = read_fastq(&b"@bad\nACNT\n+\nIIII\n@good\nAC\n+\nII\n"[..]);
+        let err = records.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "record 'bad': invalid nucleotide 'N' at position 2"
+        );
+        assert_eq!(records.next().unwrap().unwrap().id, "good");
+        assert!(records.next().is_none());
+
+        for (fastq, msg) in &[
+            (
+                &b"@r\nACGT\n+\nIII\n@r2\nA\n+\nI\n"[..],
+                "record 'r': sequence of length 4 but 3 qualities",
+            ),
+            (
+                &b"@r\nACGT\nIIII\n"[..],
+                "record 'r': expected a '+' separator, found 'IIII'",
+            ),
+            (
+                &b"@r\nACGT\n"[..],
+                "record 'r': unexpected end of file, expected a '+' separator",
+            ),
+            (
+                &b"r\nACGT\n+\nIIII\n"[..],
+                "expected '@' at the start of a record, found 'r'",
+            ),
+        ] {
+            let mut records = read_fastq(*fastq);
+            assert_eq!(records.next().unwrap().unwrap_err().to_string(), *msg);
+            assert!(records.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_read_fasta_invalid_base() {
+        let mut records = read_fasta(&b">bad\nACNT\n>good\nacgt\n"[..]);
+        let err = records.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "record 'bad': invalid nucleotide 'N' at position 2"
+        );
+        let (id, kmer) = records.next().unwrap().unwrap();
+        assert_eq!(id, "good");
+        assert_eq!(kmer, Kmer::from_literal("ACGT"));
+        assert!(records.next().is_none());
+    }
+}
@@ -4,3 +4,4 @@ pub mod bed;
 pub mod fasta;
 pub mod fastq;
 pub mod gff;
+pub mod kmer;
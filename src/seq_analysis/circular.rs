@@ -0,0 +1,96 @@
+//! Utilities for circular sequences, e.g. plasmids or mitochondrial genomes.
+//!
+//! A circular molecule can be linearized at any position and read from either strand. The
+//! canonical rotation is a representation that is independent of both choices, such that
+//! different linearizations of the same molecule can be collapsed.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::seq_analysis::circular::canonical_rotation;
+//!
+//! assert_eq!(canonical_rotation(b"GTACCA"), canonical_rotation(b"CCAGTA"));
+//! ```
+
+use crate::alphabets::dna;
+
+/// Return the start of the lexicographically smallest rotation of `text` using Booth's
+/// algorithm. Complexity: O(n).
+fn least_rotation(text: &[u8]) -> usize {
+    let n = text.len();
+    let doubled: Vec<u8> = text.iter().chain(text.iter()).cloned().collect();
+    let mut failure = vec![-1isize; 2 * n];
+    let mut k = 0;
+    for j in 1..2 * n {
+        let c = doubled[j];
+        let mut i = failure[j - k - 1];
+        while i != -1 && c != doubled[k + i as usize + 1] {
+            if c < doubled[k + i as usize + 1] {
+                k = j - i as usize - 1;
+            }
+            i = failure[i as usize];
+        }
+        if c != doubled[(k as isize + i + 1) as usize] {
+            // here, i == -1
+            if c < doubled[k] {
+                k = j;
+            }
+            failure[j - k] = -1;
+        } else {
+            failure[j - k] = i + 1;
+        }
+    }
+    k
+}
+
+/// Return the lexicographically smallest rotation of `text`.
+fn min_rotation(text: &[u8]) -> Vec<u8> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    let start = least_rotation(text);
+    text[start..]
+        .iter()
+        .chain(&text[..start])
+        .cloned()
+        .collect()
+}
+
+/// Return the canonical form of a circular DNA sequence: the lexicographically smallest rotation
+/// of either the sequence or its reverse complement. Two linearizations of the same circular
+/// molecule, from either strand, yield the same canonical rotation.
+/// Since casing is preserved by the reverse complement, sequences should be given in
+/// consistent case.
+///
+/// Complexity: O(n).
+pub fn canonical_rotation(seq: &[u8]) -> Vec<u8> {
+    let forward = min_rotation(seq);
+    let reverse = min_rotation(&dna::revcomp(seq));
+    forward.min(reverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_rotation() {
+        assert_eq!(min_rotation(b"CABBA"), b"ABBAC");
+        assert_eq!(min_rotation(b"GCAT"), b"ATGC");
+        assert_eq!(min_rotation(b"AAAA"), b"AAAA");
+        assert_eq!(min_rotation(b"BABA"), b"ABAB");
+        assert_eq!(min_rotation(b""), b"");
+    }
+
+    #[test]
+    fn test_canonical_rotation() {
+        let seq = b"GTTACCGATGCAGTAC";
+        let canonical = canonical_rotation(seq);
+        for shift in 0..seq.len() {
+            let linearized: Vec<u8> = seq[shift..].iter().chain(&seq[..shift]).cloned().collect();
+            assert_eq!(canonical_rotation(&linearized), canonical);
+            assert_eq!(canonical_rotation(&dna::revcomp(&linearized)), canonical);
+        }
+        assert_ne!(canonical_rotation(b"GTTACCGATGCAGTAA"), canonical);
+    }
+}
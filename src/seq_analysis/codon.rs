@@ -0,0 +1,150 @@
+//! Codon-wise reading of DNA sequences using the standard genetic code.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::seq_analysis::codon::CodonReader;
+//!
+//! let seq = b"CCATGAAATAAGG";
+//! let protein: Vec<u8> = CodonReader::new(seq, 2)
+//!     .map(|codon| codon.amino_acid)
+//!     .collect();
+//! assert_eq!(protein, b"MK*");
+//! ```
+
+const AMINO_ACIDS: &[u8; 64] = b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG";
+
+/// Rank of a nucleotide in the `TCAG` order of the standard codon table.
+fn rank(nucleotide: u8) -> Option<usize> {
+    match nucleotide {
+        b'T' | b't' | b'U' | b'u' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'A' | b'a' => Some(2),
+        b'G' | b'g' => Some(3),
+        _ => None,
+    }
+}
+
+/// Translate a codon into its amino acid (one-letter code) according to the standard genetic
+/// code. Stop codons are translated into `*` and codons containing other characters than
+/// `A`, `C`, `G`, `T` or `U` (in any case) into `X`.
+///
+/// # Example
+///
+/// ```
+/// use bio::seq_analysis::codon::translate;
+///
+/// assert_eq!(translate(b"ATG"), b'M');
+/// assert_eq!(translate(b"uga"), b'*');
+/// assert_eq!(translate(b"ANG"), b'X');
+/// ```
+pub fn translate(codon: &[u8; 3]) -> u8 {
+    match (rank(codon[0]), rank(codon[1]), rank(codon[2])) {
+        (Some(a), Some(b), Some(c)) => AMINO_ACIDS[16 * a + 4 * b + c],
+        _ => b'X',
+    }
+}
+
+/// A codon read from a sequence.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Codon {
+    /// Position of the first nucleotide of the codon in the sequence.
+    pub position: usize,
+    /// The nucleotides of the codon.
+    pub nucleotides: [u8; 3],
+    /// The encoded amino acid (one-letter code), see `translate`.
+    pub amino_acid: u8,
+    /// Whether the codon is the start codon `ATG`.
+    pub is_start: bool,
+    /// Whether the codon is one of the stop codons `TAA`, `TAG` or `TGA`.
+    pub is_stop: bool,
+}
+
+impl Codon {
+    fn new(position: usize, nucleotides: [u8; 3]) -> Self {
+        let amino_acid = translate(&nucleotides);
+        Codon {
+            position,
+            nucleotides,
+            amino_acid,
+            is_start: amino_acid == b'M',
+            is_stop: amino_acid == b'*',
+        }
+    }
+}
+
+/// Iterator over the codons of a sequence in a given reading frame. A trailing incomplete codon
+/// is ignored.
+pub struct CodonReader<'a> {
+    seq: &'a [u8],
+    position: usize,
+}
+
+impl<'a> CodonReader<'a> {
+    /// Read the codons of `seq` in the given frame, i.e. starting at offset `frame` (0, 1 or 2).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame` is larger than 2.
+    pub fn new(seq: &'a [u8], frame: usize) -> Self {
+        assert!(frame < 3, "reading frame must be 0, 1 or 2");
+        CodonReader {
+            seq,
+            position: frame,
+        }
+    }
+}
+
+impl<'a> Iterator for CodonReader<'a> {
+    type Item = Codon;
+
+    fn next(&mut self) -> Option<Codon> {
+        if self.position + 3 > self.seq.len() {
+            return None;
+        }
+        let position = self.position;
+        self.position += 3;
+        let nucleotides = [
+            self.seq[position],
+            self.seq[position + 1],
+            self.seq[position + 2],
+        ];
+        Some(Codon::new(position, nucleotides))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codon_reader() {
+        let seq = b"CCATGAAATAAGG";
+        let codons: Vec<Codon> = CodonReader::new(seq, 2).collect();
+        assert_eq!(codons.len(), 3);
+        assert_eq!(codons[0].position, 2);
+        assert!(codons[0].is_start);
+        assert!(!codons[0].is_stop);
+        assert_eq!(codons[1].amino_acid, b'K');
+        assert!(!codons[1].is_start && !codons[1].is_stop);
+        assert_eq!(codons[2].position, 8);
+        assert_eq!(&codons[2].nucleotides, b"TAA");
+        assert!(codons[2].is_stop);
+
+        let positions: Vec<usize> = CodonReader::new(seq, 0).map(|c| c.position).collect();
+        assert_eq!(positions, vec![0, 3, 6, 9]);
+        assert_eq!(CodonReader::new(b"AC", 0).count(), 0);
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(translate(b"TTT"), b'F');
+        assert_eq!(translate(b"TGG"), b'W');
+        assert_eq!(translate(b"GGG"), b'G');
+        for stop in &[b"TAA", b"TAG", b"TGA"] {
+            assert_eq!(translate(stop), b'*');
+        }
+        assert_eq!(translate(b"AUG"), b'M');
+        assert_eq!(translate(b"NNN"), b'X');
+    }
+}
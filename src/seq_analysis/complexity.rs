@@ -0,0 +1,130 @@
+//! Detection of low-complexity regions, e.g. tandem repeats or homopolymer runs, by the
+//! entropy of their k-mer composition.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::seq_analysis::complexity::mask_low_complexity;
+//!
+//! let mut seq = b"CACACACACACACACACACA".to_vec();
+//! mask_low_complexity(&mut seq, 10, 3, 0.5);
+//! assert_eq!(seq, b"cacacacacacacacacaca");
+//! ```
+
+use std::collections::HashMap;
+
+/// Shannon entropy of the k-mer composition of `seq`, normalized to `[0, 1]` by the largest
+/// entropy possible for that many k-mers, i.e. `log2(min(4^k, n))` for `n` k-mers. Uppercase
+/// and lowercase characters are treated alike. Sequences with fewer than two k-mers have an
+/// entropy of 0.
+///
+/// # Example
+///
+/// ```
+/// use bio::seq_analysis::complexity::kmer_entropy;
+///
+/// assert_eq!(kmer_entropy(b"AAAAAAAA", 2), 0.0);
+/// assert_eq!(kmer_entropy(b"ACGT", 1), 1.0);
+/// ```
+pub fn kmer_entropy(seq: &[u8], k: usize) -> f64 {
+    if k == 0 || seq.len() < k {
+        return 0.0;
+    }
+    let mut counts = HashMap::new();
+    for window in seq.windows(k) {
+        *counts.entry(window.to_ascii_uppercase()).or_insert(0) += 1;
+    }
+    normalized_entropy(&counts, seq.len() - k + 1, k)
+}
+
+/// Soft-mask (lowercase) all windows of `window` bases of `seq` whose k-mer entropy (see
+/// `kmer_entropy`) is below `threshold`. A sequence shorter than `window` is treated as a
+/// single window. Windows of fewer than two k-mers carry no information on their complexity
+/// and are left unmasked.
+///
+/// Complexity: O(n * w), where n is the length of the sequence and w the window size.
+pub fn mask_low_complexity(seq: &mut [u8], window: usize, k: usize, threshold: f64) {
+    if k == 0 || seq.len() < k {
+        return;
+    }
+    let window = window.min(seq.len()).max(k);
+    let n = window - k + 1;
+    if n < 2 {
+        return;
+    }
+
+    // k-mer counts of the current window, updated as the window slides along the sequence
+    let mut counts: HashMap<Vec<u8>, usize> = HashMap::new();
+    for kmer in seq[..window].windows(k) {
+        *counts.entry(kmer.to_ascii_uppercase()).or_insert(0) += 1;
+    }
+    let mut low_complexity = Vec::new();
+    for start in 0..=seq.len() - window {
+        if start > 0 {
+            let removed = seq[start - 1..start - 1 + k].to_ascii_uppercase();
+            let count = counts.get_mut(&removed).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&removed);
+            }
+            let added = &seq[start + window - k..start + window];
+            *counts.entry(added.to_ascii_uppercase()).or_insert(0) += 1;
+        }
+        if normalized_entropy(&counts, n, k) < threshold {
+            low_complexity.push(start);
+        }
+    }
+    for start in low_complexity {
+        seq[start..start + window].make_ascii_lowercase();
+    }
+}
+
+fn normalized_entropy(counts: &HashMap<Vec<u8>, usize>, n: usize, k: usize) -> f64 {
+    if n < 2 {
+        return 0.0;
+    }
+    let entropy: f64 = counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / n as f64;
+            -p * p.log2()
+        })
+        .sum();
+    let max_entropy = ((n as f64).min(4f64.powi(k as i32))).log2();
+    (entropy / max_entropy).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kmer_entropy() {
+        assert_eq!(kmer_entropy(b"AAAAAAAAAA", 3), 0.0);
+        assert_eq!(kmer_entropy(b"aaaaAAAA", 3), 0.0);
+        assert!(kmer_entropy(b"CACACACACACACACACACA", 3) < 0.3);
+        assert!(kmer_entropy(b"ATGCGTACGTTAGCATCGGATCGATCGTAGCTAGCATGCA", 3) > 0.8);
+        assert_eq!(kmer_entropy(b"ACG", 3), 0.0);
+    }
+
+    #[test]
+    fn test_mask_low_complexity() {
+        let complex = b"ATGCGTACGTTAGCATCGGATCGATCGTAGCTAGCATGCATCGATCGGCTAGCGCATTA";
+        let seq = [&complex[..], &b"CA".repeat(20), complex].concat();
+        let mut masked = seq.clone();
+        mask_low_complexity(&mut masked, 20, 3, 0.5);
+
+        let repeat = complex.len()..complex.len() + 40;
+        assert!(masked[repeat.clone()].iter().all(u8::is_ascii_lowercase));
+        assert_eq!(&masked[..30], &seq[..30]);
+        assert_eq!(&masked[repeat.end + 10..], &seq[repeat.end + 10..]);
+        assert!(masked.eq_ignore_ascii_case(&seq));
+
+        let mut short = b"ACG".to_vec();
+        mask_low_complexity(&mut short, 20, 3, 0.5);
+        assert_eq!(short, b"ACG");
+        let mut short = b"AAAA".to_vec();
+        mask_low_complexity(&mut short, 20, 3, 0.5);
+        assert_eq!(short, b"aaaa");
+    }
+}
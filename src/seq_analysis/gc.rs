@@ -58,6 +58,35 @@ pub fn gc3_content<C: Borrow<u8>, T: IntoIterator<Item = C>>(sequence: T) -> f32
     gcn_content(sequence, 3usize)
 }
 
+/// Returns the cumulative GC skew of a sequence, i.e. for each position the number of
+/// guanines minus the number of cytosines up to and including that position. The minimum and
+/// maximum of the cumulative skew of a bacterial genome hint at its origin and terminus of
+/// replication.
+///
+/// Lowercase bases are counted like uppercase ones, while `A`, `T`, `N` and any other character
+/// leave the skew unchanged.
+///
+/// # Example
+///
+/// ```
+/// use bio::seq_analysis::gc::gc_skew;
+///
+/// assert_eq!(gc_skew(b"GGCAtcNg"), vec![1, 2, 1, 1, 1, 0, 0, 1]);
+/// ```
+pub fn gc_skew(sequence: &[u8]) -> Vec<i64> {
+    sequence
+        .iter()
+        .scan(0, |skew, &n| {
+            match n {
+                b'g' | b'G' => *skew += 1,
+                b'c' | b'C' => *skew -= 1,
+                _ => {}
+            }
+            Some(*skew)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +100,13 @@ mod tests {
         let gc100 = b"GCGC";
         assert_eq!(gc_content(gc100), 1.0);
     }
+
+    #[test]
+    fn test_gc_skew() {
+        assert_eq!(gc_skew(b"GCGC"), vec![1, 0, 1, 0]);
+        assert_eq!(gc_skew(b"CCCATG"), vec![-1, -2, -3, -3, -3, -2]);
+        assert_eq!(gc_skew(b"ggNNcc"), vec![1, 2, 2, 2, 1, 0]);
+        assert_eq!(gc_skew(b"ATAT"), vec![0; 4]);
+        assert!(gc_skew(b"").is_empty());
+    }
 }
@@ -0,0 +1,39 @@
+//! Homopolymer compression, i.e. collapsing runs of the same base into a single base. Long reads
+//! (e.g. Oxford Nanopore or PacBio) frequently get the length of homopolymer runs wrong, so
+//! comparing compressed sequences ignores these errors.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::seq_analysis::homopolymer::homopolymer_compress;
+//!
+//! assert_eq!(homopolymer_compress(b"AAACCGT"), b"ACGT");
+//! ```
+
+/// Collapse each run of identical bases of `seq` into a single base. Uppercase and lowercase
+/// characters are treated alike, and the first base of each run is kept.
+pub fn homopolymer_compress(seq: &[u8]) -> Vec<u8> {
+    let mut compressed: Vec<u8> = Vec::with_capacity(seq.len());
+    for &base in seq {
+        match compressed.last() {
+            Some(last) if last.eq_ignore_ascii_case(&base) => {}
+            _ => compressed.push(base),
+        }
+    }
+    compressed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_homopolymer_compress() {
+        assert_eq!(homopolymer_compress(b"AAACCGT"), b"ACGT");
+        assert_eq!(homopolymer_compress(b"GGATTTTACCCCAGG"), b"GATACAG");
+        assert_eq!(homopolymer_compress(b"ACGT"), b"ACGT");
+        assert_eq!(homopolymer_compress(b"TTTTT"), b"T");
+        assert_eq!(homopolymer_compress(b"AAaaCcg"), b"ACg");
+        assert!(homopolymer_compress(b"").is_empty());
+    }
+}
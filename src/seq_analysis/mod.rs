@@ -5,5 +5,9 @@
 
 //! Sequence analysis algorithms.
 
+pub mod circular;
+pub mod codon;
+pub mod complexity;
 pub mod gc;
+pub mod homopolymer;
 pub mod orf;
@@ -9,7 +9,7 @@ mod fastexp;
 pub use self::fastexp::FastExp;
 
 mod text;
-pub use self::text::{trim_newline, Text, TextSlice};
+pub use self::text::{trim_newline, trim_ns, Text, TextSlice};
 
 mod interval;
 pub use self::interval::Interval;
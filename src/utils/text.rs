@@ -10,8 +10,27 @@ pub fn trim_newline(s: &mut String) {
     }
 }
 
+/// Remove leading and trailing runs of ambiguous bases (`N` or `n`) from the given text.
+/// Internal `N`s are kept.
+///
+/// # Example
+///
+/// ```
+/// use bio::utils::trim_ns;
+///
+/// assert_eq!(trim_ns(b"NNACGTnN"), b"ACGT");
+/// assert_eq!(trim_ns(b"ACNGT"), b"ACNGT");
+/// ```
+pub fn trim_ns(seq: TextSlice<'_>) -> TextSlice<'_> {
+    let is_n = |c: &u8| c == &b'N' || c == &b'n';
+    let start = seq.iter().position(|c| !is_n(c)).unwrap_or(seq.len());
+    let end = seq.iter().rposition(|c| !is_n(c)).map_or(start, |pos| pos + 1);
+    &seq[start..end]
+}
+
 #[cfg(test)]
 mod tests {
+    use super::trim_ns;
     use std::ops::Deref;
 
     /// This function demonstrates the use of the IntoSequenceIterator alias, which takes both
@@ -34,4 +53,13 @@ mod tests {
         // keep ownership
         println!("{:?}", s);
     }
+
+    #[test]
+    fn test_trim_ns() {
+        assert_eq!(trim_ns(b"NNACGTNN"), b"ACGT");
+        assert_eq!(trim_ns(b"nNACGT"), b"ACGT");
+        assert_eq!(trim_ns(b"ACNGT"), b"ACNGT");
+        assert_eq!(trim_ns(b"NNNN"), b"");
+        assert_eq!(trim_ns(b""), b"");
+    }
 }
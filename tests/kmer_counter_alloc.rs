@@ -0,0 +1,58 @@
+//! Allocation count of `KmerCounter::add_sequence`. This is a separate test target without the
+//! test harness, such that the counting allocator does not replace the allocator of the other
+//! integration tests and no other test allocates concurrently.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bio::data_structures::kmer::counter::KmerCounter;
+use bio::data_structures::kmer::Kmer;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+fn add_sequence_allocates_per_distinct_kmer() {
+    let seq = b"GATTACACAGATTCCA".repeat(1000);
+    let k = 11;
+
+    let mut naive: HashMap<Kmer, u64> = HashMap::new();
+    let naive_allocations = allocations(|| {
+        for window in seq.windows(k) {
+            *naive.entry(Kmer::new(k, window).canonical()).or_insert(0) += 1;
+        }
+    });
+
+    let mut counter = KmerCounter::new(k, true);
+    let counter_allocations = allocations(|| counter.add_sequence(&seq));
+
+    assert_eq!(counter.len(), naive.len());
+    assert!(naive_allocations >= seq.len() - k + 1);
+    assert!(counter_allocations * 100 < naive_allocations);
+}
+
+fn main() {
+    add_sequence_allocates_per_distinct_kmer();
+    println!("test add_sequence_allocates_per_distinct_kmer ... ok");
+}